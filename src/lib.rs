@@ -29,15 +29,25 @@
 //! | Type                            | Notes                                                     |
 //! | ------------------------------- | -------------------------------------------------------   |
 //! | `{i,u}8..{i,u}128`, `{i,u}size` | MySql int/uint will be converted, bytes will be parsed.<br>⚠️ Note that range of `{i,u}128` is greater than supported by MySql integer types but it'll be serialized anyway (as decimal bytes string). |
+//! | `std::num::NonZero*`            | As the corresponding primitive, zero is rejected          |
 //! | `bool`                          | MySql int {`0`, `1`} or bytes {`"0x30"`, `"0x31"`}        |
 //! | `Vec<u8>`                       | MySql bytes                                               |
+//! | `[u8; N]`                       | MySql bytes of length exactly `N`                         |
 //! | `String`                        | MySql bytes parsed as utf8                                |
-//! | `Duration` (`std` and `time`)   | MySql time or bytes parsed as MySql time string           |
+//! | `Box<str>`, `Cow<'static, str>` | MySql bytes parsed as utf8                                |
+//! | `char`                          | MySql bytes parsed as utf8 string of exactly one char     |
+//! | `Duration` (`std` and `time`)   | MySql time or bytes parsed as MySql time string.<br>⚠️ Note that negative time is only supported by `time::Duration`. |
 //! | `time::Timespec`                | MySql date or bytes parsed as MySql date string           |
+//! | `time03::{Date, Time}`          | MySql date/time or bytes parsed as MySql date/time string (`time03` feature) |
 //! | `chrono::NaiveTime`             | MySql date or bytes parsed as MySql date string           |
 //! | `chrono::NaiveDate`             | MySql date or bytes parsed as MySql date string           |
 //! | `chrono::NaiveDateTime`         | MySql date or bytes parsed as MySql date string           |
-//! | `uuid::Uuid`                    | MySql bytes parsed using `Uuid::from_slice`               |
+//! | `chrono::DateTime<Tz>`          | Serialized as MySql date holding UTC wall-clock time.<br>`DateTime<Utc>` and `DateTime<FixedOffset>` are parsed from MySql date or string as UTC |
+//! | `chrono_tz::Tz`                 | MySql bytes parsed as IANA time zone name (`chrono-tz` feature) |
+//! | `uuid::Uuid`                    | MySql bytes parsed using `Uuid::from_slice` or, for 32 or 36 bytes, `Uuid::parse_str` |
+//! | `std::net::{IpAddr, Ipv4Addr, Ipv6Addr}` | MySql bytes parsed as textual IP address         |
+//! | `mysql_common::value::convert::Point` | MySql bytes parsed as SRID-prefixed WKB point (`geo` feature) |
+//! | `semver::Version`               | MySql bytes parsed using `Version::parse` (`semver` feature) |
 //! | `serde_json::Value`             | MySql bytes parsed using `serde_json::from_str`           |
 //! | `mysql_common::Deserialized<T : DeserializeOwned>` | MySql bytes parsed using `serde_json::from_str` |
 //! | `Option<T: FromValue>`          | Must be used for nullable columns to avoid errors         |
@@ -57,11 +67,21 @@ extern crate test;
 
 pub use bigdecimal;
 pub use chrono;
+#[cfg(feature = "chrono-tz")]
+pub use chrono_tz;
+#[cfg(feature = "data-encoding")]
+pub use data_encoding;
+#[cfg(feature = "encoding_rs")]
+pub use encoding_rs;
 pub use num_bigint;
 pub use rust_decimal;
+#[cfg(feature = "semver")]
+pub use semver;
 pub use serde;
 pub use serde_json;
 pub use time;
+#[cfg(feature = "time03")]
+pub use time03;
 pub use uuid;
 
 /// This macro is a convenient way to pass named parameters to a statement.
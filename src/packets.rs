@@ -53,6 +53,7 @@ pub struct Column {
     flags: ColumnFlags,
     column_type: ColumnType,
     decimals: u8,
+    default_value: Option<(usize, usize)>,
 }
 
 /// Converts column-packet payload to an instance of `Column` structure.
@@ -60,7 +61,76 @@ pub fn column_from_payload(payload: Vec<u8>) -> io::Result<Column> {
     Column::from_payload(payload)
 }
 
+/// Creates a column of `db.table` for tests.
+#[cfg(test)]
+pub(crate) fn test_column(
+    table: &str,
+    name: &str,
+    column_type: ColumnType,
+    column_length: u32,
+    flags: ColumnFlags,
+    default_value: Option<&[u8]>,
+) -> Column {
+    let mut payload = b"\x03def".to_vec();
+    for field in &["db", table, table, name, name] {
+        payload.write_lenenc_str(field.as_bytes()).unwrap();
+    }
+    payload.push(0x0c);
+    payload.write_u16::<LE>(UTF8MB4_GENERAL_CI).unwrap();
+    payload.write_u32::<LE>(column_length).unwrap();
+    payload.push(column_type as u8);
+    payload.write_u16::<LE>(flags.bits()).unwrap();
+    payload.extend_from_slice(&[0x00, 0x00, 0x00]);
+    if let Some(default_value) = default_value {
+        payload.write_lenenc_str(default_value).unwrap();
+    }
+    column_from_payload(payload).unwrap()
+}
+
 impl Column {
+    /// Creates a column with the given name and type (schema and table fields are empty).
+    ///
+    /// Useful for columns that are not received from a server, i.e. derived columns.
+    pub fn new(name: &[u8], column_type: ColumnType) -> Column {
+        let mut payload = b"\x03def".to_vec();
+        for field in &[&[][..], &[], &[], name, name] {
+            payload.write_lenenc_str(field).unwrap();
+        }
+        payload.push(0x0c);
+        payload.write_u16::<LE>(UTF8MB4_GENERAL_CI).unwrap();
+        payload.write_u32::<LE>(0).unwrap();
+        payload.push(column_type as u8);
+        payload.extend_from_slice(&[0x00, 0x00, 0x00, 0x00, 0x00]);
+        Column::from_payload(payload).expect("valid column payload")
+    }
+
+    /// Returns a copy of this column with the name field replaced by `name`.
+    ///
+    /// Other fields (including org_name) are preserved.
+    pub fn with_name(&self, name: &[u8]) -> Column {
+        let mut payload = b"\x03def".to_vec();
+        for field in &[
+            self.schema_ref(),
+            self.table_ref(),
+            self.org_table_ref(),
+            name,
+            self.org_name_ref(),
+        ] {
+            payload.write_lenenc_str(field).unwrap();
+        }
+        payload.push(0x0c);
+        payload.write_u16::<LE>(self.character_set).unwrap();
+        payload.write_u32::<LE>(self.column_length).unwrap();
+        payload.push(self.column_type as u8);
+        payload.write_u16::<LE>(self.flags.bits()).unwrap();
+        payload.push(self.decimals);
+        payload.extend_from_slice(&[0x00, 0x00]);
+        if let Some(default_value) = self.default_value_ref() {
+            payload.write_lenenc_str(default_value).unwrap();
+        }
+        Column::from_payload(payload).expect("valid column payload")
+    }
+
     /// Converts column-packet payload to an instance of `Column` structure.
     fn from_payload(payload: Vec<u8>) -> io::Result<Column> {
         let schema;
@@ -73,6 +143,7 @@ impl Column {
         let column_type;
         let flags;
         let decimals;
+        let default_value;
 
         {
             // Skip "def"
@@ -88,6 +159,14 @@ impl Column {
             column_type = reader.read_u8()?;
             flags = reader.read_u16::<LE>()?;
             decimals = reader.read_u8()?;
+            // Skip filler. Default value is only sent in response to COM_FIELD_LIST.
+            default_value = match reader.get(2..) {
+                Some(rest) if !rest.is_empty() && rest[0] != 0xfb => {
+                    let mut rest = rest;
+                    Some(get_offset_and_len!(payload, read_lenenc_str!(&mut rest)?))
+                }
+                _ => None,
+            };
         }
 
         Ok(Column {
@@ -102,6 +181,7 @@ impl Column {
             flags: ColumnFlags::from_bits_truncate(flags),
             column_type: ColumnType::from(column_type),
             decimals,
+            default_value,
         })
     }
 
@@ -183,6 +263,15 @@ impl Column {
     pub fn org_name_str(&self) -> Cow<'_, str> {
         String::from_utf8_lossy(self.org_name_ref())
     }
+
+    /// Returns value of the default field of a column packet as a byte slice.
+    ///
+    /// Server only sends this field in response to COM_FIELD_LIST, so it is `None` for columns
+    /// of a result set or if the column has no default.
+    pub fn default_value_ref(&self) -> Option<&[u8]> {
+        self.default_value
+            .map(|(offset, len)| &self.payload[offset..offset + len])
+    }
 }
 
 /// Represents parsed change in session state (part of MySql's Ok packet).
@@ -0,0 +1,37 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+
+/// Character set of a textual column (see `Row::get_with_charset`).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum Charset {
+    /// MySql `latin1`. MySql's `latin1` is actually `cp1252`, so it is decoded as Windows-1252.
+    Latin1,
+    /// Windows-1252.
+    Windows1252,
+    /// MySql `utf8` and `utf8mb4`.
+    Utf8,
+}
+
+impl Charset {
+    fn encoding(self) -> &'static Encoding {
+        match self {
+            Charset::Latin1 | Charset::Windows1252 => WINDOWS_1252,
+            Charset::Utf8 => UTF_8,
+        }
+    }
+
+    /// Decodes `bytes` replacing malformed sequences with the replacement character.
+    pub fn decode(self, bytes: &[u8]) -> String {
+        self.encoding()
+            .decode_without_bom_handling(bytes)
+            .0
+            .into_owned()
+    }
+}
@@ -1991,22 +1991,9 @@ where
 #[bench]
 fn bench_from_row(bencher: &mut test::Bencher) {
     use crate::constants::ColumnType;
-    use crate::io::WriteMysqlExt;
-    use crate::packets::column_from_payload;
     use crate::packets::Column;
     use crate::value::Value;
 
-    fn col(name: &str, ty: ColumnType) -> Column {
-        let mut payload = b"\x00def".to_vec();
-        for _ in 0..5 {
-            payload.write_lenenc_str(name.as_bytes()).unwrap();
-        }
-        payload.extend_from_slice(&b"_\x2d\x00\xff\xff\xff\xff"[..]);
-        payload.push(ty as u8);
-        payload.extend_from_slice(&b"\x00\x00\x00"[..]);
-        column_from_payload(payload).unwrap()
-    }
-
     let row = Row {
         values: vec![
             Some(Value::Bytes(b"12.3456789".to_vec())),
@@ -2015,10 +2002,10 @@ fn bench_from_row(bencher: &mut test::Bencher) {
             Some(Value::Int(0xF0000000)),
         ],
         columns: std::sync::Arc::new(vec![
-            col("foo", ColumnType::MYSQL_TYPE_STRING),
-            col("foo", ColumnType::MYSQL_TYPE_TINY),
-            col("foo", ColumnType::MYSQL_TYPE_SHORT),
-            col("foo", ColumnType::MYSQL_TYPE_LONG),
+            Column::new(b"foo", ColumnType::MYSQL_TYPE_STRING),
+            Column::new(b"foo", ColumnType::MYSQL_TYPE_TINY),
+            Column::new(b"foo", ColumnType::MYSQL_TYPE_SHORT),
+            Column::new(b"foo", ColumnType::MYSQL_TYPE_LONG),
         ]),
     };
 
@@ -0,0 +1,97 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+use std::ops::Deref;
+use std::sync::Arc;
+
+use super::Row;
+
+/// Immutable, cheaply-cloneable view of a `Row` (see `Row::freeze`).
+///
+/// It dereferences to `Row`, so read-only accessors (`get`, `get_opt`, `as_ref`, indexing, ...)
+/// are available, but `take` and other mutating methods are not. Cloning a `FrozenRow` only bumps
+/// a reference count, while conversions still clone the values they convert.
+#[derive(Clone, PartialEq)]
+pub struct FrozenRow(Arc<Row>);
+
+impl FrozenRow {
+    /// Returns `true` if both handles point to the same frozen row.
+    pub fn ptr_eq(&self, other: &FrozenRow) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Unwraps the row if this is the only handle, otherwise clones it.
+    pub fn into_row(self) -> Row {
+        Arc::try_unwrap(self.0).unwrap_or_else(|row| (*row).clone())
+    }
+}
+
+impl From<Row> for FrozenRow {
+    fn from(row: Row) -> FrozenRow {
+        FrozenRow(Arc::new(row))
+    }
+}
+
+impl Deref for FrozenRow {
+    type Target = Row;
+
+    fn deref(&self) -> &Row {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::constants::ColumnType;
+    use crate::packets::Column;
+    use crate::row::{new_row, FrozenRow};
+    use crate::value::Value;
+
+    fn frozen() -> FrozenRow {
+        let columns = vec![
+            Column::new(b"id", ColumnType::MYSQL_TYPE_LONG),
+            Column::new(b"name", ColumnType::MYSQL_TYPE_VAR_STRING),
+        ];
+        let mut row = new_row(vec![Value::Int(42), Value::from("foo")], Arc::new(columns));
+        row.take::<String, _>("name");
+        row.freeze()
+    }
+
+    #[test]
+    fn should_share_frozen_row() {
+        let first = frozen();
+        let second = first.clone();
+        assert!(first.ptr_eq(&second));
+
+        assert_eq!(first.get::<i32, _>("id"), Some(42));
+        assert_eq!(second.get::<i32, _>(0), Some(42));
+        assert_eq!(first["id"], Value::Int(42));
+        assert_eq!(second[0], Value::Int(42));
+        assert_eq!(second.as_ref(1), None);
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.values(), &[Some(Value::Int(42)), None][..]);
+        assert_eq!(
+            second
+                .iter()
+                .map(|(column, value)| (column.name_str(), value))
+                .collect::<Vec<_>>(),
+            vec![("id".into(), Some(&Value::Int(42))), ("name".into(), None)]
+        );
+    }
+
+    #[test]
+    fn should_unwrap_frozen_row() {
+        let first = frozen();
+        let second = first.clone();
+        let row = first.into_row();
+        assert_eq!(row, *second);
+        assert_eq!(second.into_row(), row);
+    }
+}
@@ -6,14 +6,36 @@
 // option. All files in the project carrying such notice may not be copied,
 // modified, or distributed except according to those terms.
 
+use crate::constants::{ColumnFlags, ColumnType};
 use crate::packets::Column;
-use crate::value::convert::{from_value, from_value_opt, FromValue, FromValueError};
+use crate::row::convert::{FromRow, FromRowError};
+use crate::value::convert::{
+    from_value, from_value_opt, presence, CBool, ConversionError, FromValue, FromValueError,
+    FromValueRef, Presence,
+};
 use crate::value::Value;
+use serde_json::{Map, Value as Json};
+use std::any::Any;
+use std::error::Error;
 use std::fmt;
+use std::hash::Hasher;
+use std::iter::{FromIterator, Zip};
 use std::ops::Index;
 use std::sync::Arc;
+use std::time::Duration;
+use std::vec;
+use twox_hash::XxHash64;
 
+#[cfg(feature = "encoding_rs")]
+mod charset;
 pub mod convert;
+mod frozen;
+mod registry;
+
+#[cfg(feature = "encoding_rs")]
+pub use self::charset::Charset;
+pub use self::frozen::FrozenRow;
+pub use self::registry::{ConversionRegistry, Decoder};
 
 /// Client side representation of a MySql row.
 ///
@@ -43,6 +65,207 @@ impl fmt::Debug for Row {
     }
 }
 
+/// Single-line `col1=val1, col2=val2` rendering for logging.
+///
+/// Bytes are shown as is if valid utf8 and as `0x`-prefixed hex otherwise, other values are shown
+/// as in `Value::as_sql`. Taken columns are shown as `col=<taken>`.
+impl fmt::Display for Row {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (val, column)) in self.values.iter().zip(self.columns.iter()).enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}=", column.name_str())?;
+            match val {
+                Some(Value::Bytes(bytes)) => match std::str::from_utf8(bytes) {
+                    Ok(string) => f.write_str(string)?,
+                    Err(_) => {
+                        f.write_str("0x")?;
+                        for byte in bytes {
+                            write!(f, "{:02X}", byte)?;
+                        }
+                    }
+                },
+                Some(val) => f.write_str(&val.as_sql(false))?,
+                None => f.write_str("<taken>")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `Row` access error.
+#[derive(Debug)]
+pub enum RowError {
+    /// Column doesn't exist or its value was taken.
+    ColumnMissing,
+    /// Column value couldn't be converted to a desired type.
+    FromValueError(FromValueError),
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RowError::ColumnMissing => write!(f, "Column doesn't exist or was taken"),
+            RowError::FromValueError(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for RowError {
+    fn description(&self) -> &str {
+        match self {
+            RowError::ColumnMissing => "Column doesn't exist or was taken",
+            RowError::FromValueError(_) => "Couldn't convert the value to a desired type",
+        }
+    }
+}
+
+/// `Row::lookup` error.
+#[derive(Debug)]
+pub enum RowLookupError {
+    /// Row has no column with the given name.
+    NoSuchColumn,
+    /// Column value was taken by `Row::take` or `Row::take_opt`.
+    Taken,
+    /// Column value couldn't be converted to a desired type.
+    Conversion(FromValueError),
+}
+
+impl fmt::Display for RowLookupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RowLookupError::NoSuchColumn => write!(f, "Column doesn't exist"),
+            RowLookupError::Taken => write!(f, "Column value was taken"),
+            RowLookupError::Conversion(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for RowLookupError {
+    fn description(&self) -> &str {
+        match self {
+            RowLookupError::NoSuchColumn => "Column doesn't exist",
+            RowLookupError::Taken => "Column value was taken",
+            RowLookupError::Conversion(_) => "Couldn't convert the value to a desired type",
+        }
+    }
+}
+
+/// Mismatch between a `Row` and an expected schema (see `Row::validate`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaError {
+    /// Row has a different number of columns.
+    ColumnCount { expected: usize, actual: usize },
+    /// Column at `position` has an unexpected name.
+    ColumnName {
+        position: usize,
+        expected: String,
+        actual: String,
+    },
+    /// Column at `position` has a type that is not compatible with the expected one.
+    ColumnType {
+        position: usize,
+        name: String,
+        expected: ColumnType,
+        actual: ColumnType,
+    },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::ColumnCount { expected, actual } => write!(
+                f,
+                "Expected {} columns, but row has {} columns",
+                expected, actual
+            ),
+            SchemaError::ColumnName {
+                position,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Expected column `{}` at position {}, found `{}`",
+                expected, position, actual
+            ),
+            SchemaError::ColumnType {
+                position,
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Expected column `{}` at position {} to be of type {:?}, found {:?}",
+                name, position, expected, actual
+            ),
+        }
+    }
+}
+
+impl Error for SchemaError {
+    fn description(&self) -> &str {
+        match self {
+            SchemaError::ColumnCount { .. } => "Row has unexpected number of columns",
+            SchemaError::ColumnName { .. } => "Row has unexpected column name",
+            SchemaError::ColumnType { .. } => "Row has unexpected column type",
+        }
+    }
+}
+
+/// Rows passed to `transpose` don't share a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransposeError {
+    /// Row at `row` has columns that differ (by name or type) from columns of the first row.
+    SchemaMismatch { row: usize },
+}
+
+impl fmt::Display for TransposeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransposeError::SchemaMismatch { row } => write!(
+                f,
+                "Row {} has columns that differ from columns of the first row",
+                row
+            ),
+        }
+    }
+}
+
+impl Error for TransposeError {
+    fn description(&self) -> &str {
+        match self {
+            TransposeError::SchemaMismatch { .. } => "Rows don't share a schema",
+        }
+    }
+}
+
+/// Returns `true` if column of type `actual` is acceptable where `expected` is expected.
+///
+/// Types that differ only in storage (e.g. `VARCHAR` and `VAR_STRING`, blobs of different size,
+/// `DECIMAL` and `NEWDECIMAL`) are considered compatible.
+fn is_compatible_type(expected: ColumnType, actual: ColumnType) -> bool {
+    use crate::constants::ColumnType::*;
+
+    fn family(column_type: ColumnType) -> ColumnType {
+        match column_type {
+            MYSQL_TYPE_VARCHAR | MYSQL_TYPE_VAR_STRING | MYSQL_TYPE_STRING => MYSQL_TYPE_STRING,
+            MYSQL_TYPE_TINY_BLOB
+            | MYSQL_TYPE_MEDIUM_BLOB
+            | MYSQL_TYPE_LONG_BLOB
+            | MYSQL_TYPE_BLOB => MYSQL_TYPE_BLOB,
+            MYSQL_TYPE_DECIMAL | MYSQL_TYPE_NEWDECIMAL => MYSQL_TYPE_NEWDECIMAL,
+            MYSQL_TYPE_DATE | MYSQL_TYPE_NEWDATE => MYSQL_TYPE_DATE,
+            MYSQL_TYPE_TIMESTAMP | MYSQL_TYPE_TIMESTAMP2 => MYSQL_TYPE_TIMESTAMP,
+            MYSQL_TYPE_DATETIME | MYSQL_TYPE_DATETIME2 => MYSQL_TYPE_DATETIME,
+            MYSQL_TYPE_TIME | MYSQL_TYPE_TIME2 => MYSQL_TYPE_TIME,
+            other => other,
+        }
+    }
+
+    family(expected) == family(actual)
+}
+
 /// Creates `Row` from values and columns.
 pub fn new_row(values: Vec<Value>, columns: Arc<Vec<Column>>) -> Row {
     assert!(values.len() == columns.len());
@@ -52,6 +275,88 @@ pub fn new_row(values: Vec<Value>, columns: Arc<Vec<Column>>) -> Row {
     }
 }
 
+/// Builder of a `Row` with the given columns.
+///
+/// Unlike `new_row` it doesn't panic if the number of values doesn't match the number
+/// of columns, but returns `SchemaError::ColumnCount` from `RowBuilder::build`.
+///
+/// ```ignore
+/// let row = RowBuilder::new(columns).push(1).push("foo").build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct RowBuilder {
+    values: Vec<Value>,
+    columns: Arc<Vec<Column>>,
+}
+
+impl RowBuilder {
+    /// Creates a builder of a row with `columns`.
+    pub fn new(columns: Arc<Vec<Column>>) -> RowBuilder {
+        RowBuilder {
+            values: Vec::with_capacity(columns.len()),
+            columns,
+        }
+    }
+
+    /// Appends value of the next column.
+    pub fn push<T: Into<Value>>(mut self, value: T) -> RowBuilder {
+        self.values.push(value.into());
+        self
+    }
+
+    /// Builds the row, checking that there is a value for every column.
+    pub fn build(self) -> Result<Row, SchemaError> {
+        if self.values.len() != self.columns.len() {
+            return Err(SchemaError::ColumnCount {
+                expected: self.columns.len(),
+                actual: self.values.len(),
+            });
+        }
+        Ok(new_row(self.values, self.columns))
+    }
+}
+
+/// Column name and its values (see `transpose`).
+pub type TransposedColumn = (String, Vec<Option<Value>>);
+
+/// Converts `rows` sharing a schema into per-column vectors of values (column-major order).
+///
+/// Each entry holds a column name and its values in the order of `rows` (`None` for taken
+/// columns). Rows are expected to have columns of the same names and types.
+pub fn transpose(rows: &[Row]) -> Result<Vec<TransposedColumn>, TransposeError> {
+    let first = match rows.first() {
+        Some(first) => first,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut output = first
+        .columns
+        .iter()
+        .map(|column| {
+            (
+                column.name_str().into_owned(),
+                Vec::with_capacity(rows.len()),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    for (i, row) in rows.iter().enumerate() {
+        let same_schema = Arc::ptr_eq(&row.columns, &first.columns)
+            || (row.columns.len() == first.columns.len()
+                && row.columns.iter().zip(first.columns.iter()).all(|(a, b)| {
+                    a.name_ref() == b.name_ref() && a.column_type() == b.column_type()
+                }));
+        if !same_schema {
+            return Err(TransposeError::SchemaMismatch { row: i });
+        }
+        for ((_, values), value) in output.iter_mut().zip(row.values.iter()) {
+            values.push(value.clone());
+        }
+    }
+
+    Ok(output)
+}
+
 impl Row {
     /// Returns length of a row.
     pub fn len(&self) -> usize {
@@ -68,6 +373,81 @@ impl Row {
         self.columns.clone()
     }
 
+    /// Returns index of the first column named `name`.
+    ///
+    /// Names are matched exactly (case-sensitively), as `row["name"]` does. Server returns column
+    /// names (and aliases) as written in the query, so `SELECT Id ...` gives `"Id"`, not `"id"`.
+    /// Lookup is a linear scan, so resolve the index once and use it for every row of a result set
+    /// (rows of a result set share columns).
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        name.idx(&self.columns)
+    }
+
+    /// Returns an iterator over columns of this row and their values (`None` for taken columns).
+    pub fn iter(&self) -> impl Iterator<Item = (&Column, Option<&Value>)> {
+        self.columns
+            .iter()
+            .zip(self.values.iter().map(Option::as_ref))
+    }
+
+    /// Returns an iterator over positional pairs of values of this row and `other`.
+    ///
+    /// Shorter row is padded with `None`, taken columns also give `None`. Column metadata is
+    /// ignored, so rows are expected to be aligned (e.g. for side-by-side comparison).
+    pub fn zip_values<'a>(
+        &'a self,
+        other: &'a Row,
+    ) -> impl Iterator<Item = (Option<&'a Value>, Option<&'a Value>)> {
+        let value = |row: &'a Row, idx: usize| row.values.get(idx).and_then(Option::as_ref);
+        (0..self.len().max(other.len())).map(move |idx| (value(self, idx), value(other, idx)))
+    }
+
+    /// Returns the number of columns that were not taken (including `NULL` values).
+    pub fn count_present(&self) -> usize {
+        self.values.iter().filter(|x| x.is_some()).count()
+    }
+
+    /// Returns the number of columns that were not taken and are not `NULL`.
+    pub fn count_non_null(&self) -> usize {
+        self.values
+            .iter()
+            .filter(|x| matches!(x, Some(value) if *value != Value::NULL))
+            .count()
+    }
+
+    /// Checks that columns of this row match `expected` names and types, in order.
+    ///
+    /// Names are compared exactly. Types are compared up to storage differences
+    /// (e.g. `VARCHAR` matches `VAR_STRING`). Returns the first mismatch found.
+    pub fn validate(&self, expected: &[(&str, ColumnType)]) -> Result<(), SchemaError> {
+        for (position, (column, (name, column_type))) in
+            self.columns.iter().zip(expected.iter()).enumerate()
+        {
+            if column.name_ref() != name.as_bytes() {
+                return Err(SchemaError::ColumnName {
+                    position,
+                    expected: (*name).into(),
+                    actual: column.name_str().into_owned(),
+                });
+            }
+            if !is_compatible_type(*column_type, column.column_type()) {
+                return Err(SchemaError::ColumnType {
+                    position,
+                    name: (*name).into(),
+                    expected: *column_type,
+                    actual: column.column_type(),
+                });
+            }
+        }
+        if self.columns.len() != expected.len() {
+            return Err(SchemaError::ColumnCount {
+                expected: expected.len(),
+                actual: self.columns.len(),
+            });
+        }
+        Ok(())
+    }
+
     /// Returns reference to the value of a column with index `index` if it exists and wasn't taken
     /// by `Row::take` method.
     ///
@@ -76,6 +456,37 @@ impl Row {
         self.values.get(index).and_then(|x| x.as_ref())
     }
 
+    /// Returns references to values of `N` consecutive columns starting at index `start`.
+    ///
+    /// Returns `None` if any of columns in range doesn't exist or was taken by `Row::take`.
+    pub fn as_refs<const N: usize>(&self, start: usize) -> Option<[&Value; N]> {
+        let values = self.values.get(start..start.checked_add(N)?)?;
+        let mut output = [&Value::NULL; N];
+        for (output, value) in output.iter_mut().zip(values) {
+            *output = value.as_ref()?;
+        }
+        Some(output)
+    }
+
+    /// Collects names and values of present columns into `B`.
+    ///
+    /// Values are cloned, taken columns are skipped. Duplicate names are handled according to
+    /// semantics of `B` (e.g. `BTreeMap` keeps the last value, while `Vec` keeps all pairs).
+    pub fn collect_columns<B>(&self) -> B
+    where
+        B: FromIterator<(String, Value)>,
+    {
+        self.columns
+            .iter()
+            .zip(self.values.iter())
+            .filter_map(|(column, value)| {
+                value
+                    .as_ref()
+                    .map(|value| (column.name_str().into_owned(), value.clone()))
+            })
+            .collect()
+    }
+
     /// Will copy value at index `index` if it was not taken by `Row::take` earlier,
     /// then will convert it to `T`.
     pub fn get<T, I>(&self, index: I) -> Option<T>
@@ -91,6 +502,27 @@ impl Row {
         })
     }
 
+    /// Will copy value at index `index` and convert it to the intermediate result of `T`
+    /// conversion (see `FromValue::get_intermediate`).
+    ///
+    /// Useful to probe several types: `ConvIr::commit` gives `T`, while `ConvIr::rollback` gives
+    /// back the value, that could then be converted to another type without copying it again.
+    /// The error names `T` (see `FromValue::get_intermediate_explained`). The row itself is not
+    /// modified. Returns `None` if the column doesn't exist or its value was taken by `Row::take`
+    /// or `Row::take_opt`.
+    pub fn get_intermediate<T, I>(
+        &self,
+        index: I,
+    ) -> Option<Result<T::Intermediate, ConversionError>>
+    where
+        T: FromValue,
+        I: ColumnIndex,
+    {
+        let idx = index.idx(&self.columns)?;
+        let value = self.values.get(idx)?.as_ref()?;
+        Some(T::get_intermediate_explained(value.clone()))
+    }
+
     /// Will copy value at index `index` if it was not taken by `Row::take` or `Row::take_opt`
     /// earlier, then will attempt convert it to `T`. Unlike `Row::get`, `Row::get_opt` will
     /// allow you to directly handle errors if the value could not be converted to `T`.
@@ -106,6 +538,360 @@ impl Row {
             .and_then(|x| Some(from_value_opt::<T>(x.clone())))
     }
 
+    /// Will copy value of the column named `name` and convert it to `T`.
+    ///
+    /// Unlike `Row::get_opt`, tells a missing column (`RowLookupError::NoSuchColumn`) from
+    /// a taken one (`RowLookupError::Taken`). If column name is duplicated, then the first
+    /// occurrence is used.
+    pub fn lookup<T>(&self, name: &str) -> Result<T, RowLookupError>
+    where
+        T: FromValue,
+    {
+        let idx = name
+            .idx(&self.columns)
+            .ok_or(RowLookupError::NoSuchColumn)?;
+        let value = self.values[idx].as_ref().ok_or(RowLookupError::Taken)?;
+        from_value_opt::<T>(value.clone()).map_err(RowLookupError::Conversion)
+    }
+
+    /// Will copy value at index `index` and convert it to `T`, falling back to the column's
+    /// declared default if the value is `NULL`.
+    ///
+    /// Default is only known for columns received in response to COM_FIELD_LIST
+    /// (see `Column::default_value_ref`). Returns `None` if the column doesn't exist or was taken,
+    /// or if the value is `NULL` and there is no convertible default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the present (non-`NULL`) value is not convertible to `T` (as `Row::get` does).
+    pub fn get_or_column_default<T, I>(&self, index: I) -> Option<T>
+    where
+        T: FromValue,
+        I: ColumnIndex,
+    {
+        let idx = index.idx(&self.columns)?;
+        match self.values.get(idx)?.as_ref()? {
+            Value::NULL => self.columns[idx]
+                .default_value_ref()
+                .and_then(|default| from_value_opt::<T>(Value::Bytes(default.to_vec())).ok()),
+            value => Some(from_value::<T>(value.clone())),
+        }
+    }
+
+    /// Will copy value at index `index` and convert it to `i128` respecting the column's sign.
+    ///
+    /// If the column is `UNSIGNED` but the value is negative (e.g. `Value::Int(-1)` for
+    /// an unsigned `TINYINT`), then it is reinterpreted as an unsigned integer of the column's
+    /// width (`255` in this example). Values of signed or non-integer columns are not changed.
+    /// Returns `None` if the column doesn't exist or was taken or if the value is not an integer.
+    pub fn get_respecting_sign<I>(&self, index: I) -> Option<i128>
+    where
+        I: ColumnIndex,
+    {
+        let idx = index.idx(&self.columns)?;
+        let value = self.values.get(idx)?.as_ref()?;
+        let x = from_value_opt::<i128>(value.clone()).ok()?;
+        let column = &self.columns[idx];
+        if x < 0 && column.flags().contains(ColumnFlags::UNSIGNED_FLAG) {
+            let bits = match column.column_type() {
+                ColumnType::MYSQL_TYPE_TINY => 8,
+                ColumnType::MYSQL_TYPE_SHORT | ColumnType::MYSQL_TYPE_YEAR => 16,
+                ColumnType::MYSQL_TYPE_INT24 => 24,
+                ColumnType::MYSQL_TYPE_LONG => 32,
+                ColumnType::MYSQL_TYPE_LONGLONG => 64,
+                _ => return Some(x),
+            };
+            Some(x + (1 << bits))
+        } else {
+            Some(x)
+        }
+    }
+
+    /// Will copy time value at index `index` and convert it to its sign (`true` if negative) and
+    /// magnitude.
+    ///
+    /// Unlike `Row::get::<std::time::Duration, _>`, this keeps the sign of negative `TIME`
+    /// values. Accepts `Value::Time` (including day-carrying values) and bytes parsed as MySql
+    /// time string (as `time::Duration` does). Returns `None` if the column doesn't exist or was
+    /// taken or if the value is not a time.
+    pub fn get_signed_duration<I>(&self, index: I) -> Option<(bool, Duration)>
+    where
+        I: ColumnIndex,
+    {
+        let idx = index.idx(&self.columns)?;
+        let value = self.values.get(idx)?.as_ref()?;
+        let duration = from_value_opt::<time::Duration>(value.clone()).ok()?;
+        let is_neg = duration < time::Duration::zero();
+        let magnitude = if is_neg { -duration } else { duration };
+        magnitude.to_std().ok().map(|x| (is_neg, x))
+    }
+
+    /// Will decode bytes value at index `index` using `charset` (`encoding_rs` feature).
+    ///
+    /// Useful for legacy non-UTF-8 columns. Malformed sequences are replaced with
+    /// the replacement character. Returns `None` if the column doesn't exist or was taken,
+    /// or if the value is not `Value::Bytes`.
+    #[cfg(feature = "encoding_rs")]
+    pub fn get_with_charset<I>(&self, index: I, charset: Charset) -> Option<String>
+    where
+        I: ColumnIndex,
+    {
+        let idx = index.idx(&self.columns)?;
+        match self.values.get(idx)?.as_ref()? {
+            Value::Bytes(bytes) => Some(charset.decode(bytes)),
+            _ => None,
+        }
+    }
+
+    /// Will copy value at index `index` and convert it to `bool` according to the column type.
+    ///
+    /// * `TINYINT(1)` (MySql's conventional boolean) is `true` if nonzero (see `CBool`);
+    /// * `BIT(1)` is `true` if its byte is nonzero;
+    /// * other columns use strict `bool` conversion.
+    ///
+    /// Returns `None` if the column doesn't exist or was taken, or if the value is `NULL` or
+    /// couldn't be converted.
+    pub fn get_bool<I>(&self, index: I) -> Option<bool>
+    where
+        I: ColumnIndex,
+    {
+        let idx = index.idx(&self.columns)?;
+        let value = self.values.get(idx)?.as_ref()?.clone();
+        let column = &self.columns[idx];
+        match (column.column_type(), column.column_length()) {
+            (ColumnType::MYSQL_TYPE_TINY, 1) => from_value_opt::<CBool>(value).ok().map(|x| x.0),
+            (ColumnType::MYSQL_TYPE_BIT, 1) => match value {
+                Value::Bytes(ref bytes) if bytes.len() == 1 => Some(bytes[0] != 0),
+                value => from_value_opt::<bool>(value).ok(),
+            },
+            _ => from_value_opt::<bool>(value).ok(),
+        }
+    }
+
+    /// Maps textual value of an `ENUM` column at index `index` to a variant using `variants`.
+    ///
+    /// Names are matched case-sensitively (the server returns the value exactly as declared
+    /// in the column definition). Returns `None` if the column doesn't exist, was taken, is not
+    /// textual or doesn't match any of `variants`.
+    pub fn get_enum<E, I>(&self, index: I, variants: &[(&str, E)]) -> Option<E>
+    where
+        E: Copy,
+        I: ColumnIndex,
+    {
+        let idx = index.idx(&self.columns)?;
+        match self.values.get(idx)?.as_ref()? {
+            Value::Bytes(bytes) => variants
+                .iter()
+                .find(|(name, _)| name.as_bytes() == &bytes[..])
+                .map(|(_, variant)| *variant),
+            _ => None,
+        }
+    }
+
+    /// Converts columns named `prefix.*` to `T` (e.g. `user.id` and `user.name` for `"user"`).
+    ///
+    /// Values of matching columns are cloned into a new row, where the prefix is stripped
+    /// from column names, and then converted using `FromRow`. Taken columns are skipped.
+    /// Returns `None` if there are no matching columns.
+    pub fn get_nested<T: FromRow>(&self, prefix: &str) -> Option<Result<T, FromRowError>> {
+        let mut values = Vec::new();
+        let mut columns = Vec::new();
+        for (column, value) in self.columns.iter().zip(self.values.iter()) {
+            let name = column.name_ref();
+            let is_nested = name.len() > prefix.len()
+                && name.starts_with(prefix.as_bytes())
+                && name[prefix.len()] == b'.';
+            if let (true, Some(value)) = (is_nested, value) {
+                values.push(Some(value.clone()));
+                columns.push(column.with_name(&name[prefix.len() + 1..]));
+            }
+        }
+
+        if values.is_empty() {
+            None
+        } else {
+            Some(T::from_row_opt(Row {
+                values,
+                columns: Arc::new(columns),
+            }))
+        }
+    }
+
+    /// Will copy value at index `index` and convert it to `Option<T>`.
+    ///
+    /// Flattened accessor for nullable columns:
+    ///
+    /// * missing or taken column gives `Err(RowError::ColumnMissing)`;
+    /// * `NULL` gives `Ok(None)`;
+    /// * convertible value gives `Ok(Some(T))`;
+    /// * inconvertible value gives `Err(RowError::FromValueError(_))`.
+    pub fn get_nullable<T, I>(&self, index: I) -> Result<Option<T>, RowError>
+    where
+        T: FromValue,
+        I: ColumnIndex,
+    {
+        let value = index
+            .idx(&self.columns)
+            .and_then(|idx| self.values.get(idx))
+            .and_then(|x| x.as_ref())
+            .ok_or(RowError::ColumnMissing)?;
+        from_value_opt::<Option<T>>(value.clone()).map_err(RowError::FromValueError)
+    }
+
+    /// Will copy the first present non-`NULL` value among columns at `indices` and convert it
+    /// to `T` (client-side `COALESCE`).
+    ///
+    /// Returns `None` if all the columns are `NULL`, taken or missing. Also returns `None` if
+    /// the chosen value couldn't be converted to `T` (the remaining columns are not considered).
+    pub fn coalesce<T>(&self, indices: &[usize]) -> Option<T>
+    where
+        T: FromValue,
+    {
+        indices
+            .iter()
+            .filter_map(|&idx| self.values.get(idx).and_then(Option::as_ref))
+            .find(|value| **value != Value::NULL)
+            .and_then(|value| from_value_opt::<T>(value.clone()).ok())
+    }
+
+    /// Will convert value at index `idx` borrowing it (see `Row::to_tuple_ref`).
+    fn column_ref<'a, T: FromValueRef<'a>>(&'a self, idx: usize) -> Result<T, FromValueError> {
+        match self.values[idx] {
+            Some(ref value) => T::from_value_ref(value),
+            None => Err(FromValueError(Value::NULL)),
+        }
+    }
+
+    /// Will convert the first two values of this row to `(A, B)` without cloning them.
+    ///
+    /// Returns `None` if the row has less than two columns. Taken column gives an error
+    /// (holding `Value::NULL`).
+    pub fn to_tuple_ref<'a, A, B>(&'a self) -> Option<Result<(A, B), FromValueError>>
+    where
+        A: FromValueRef<'a>,
+        B: FromValueRef<'a>,
+    {
+        if self.len() < 2 {
+            return None;
+        }
+        Some(
+            self.column_ref(0)
+                .and_then(|a| Ok((a, self.column_ref(1)?))),
+        )
+    }
+
+    /// Same as `Row::to_tuple_ref` but for the first three values.
+    pub fn to_tuple3_ref<'a, A, B, C>(&'a self) -> Option<Result<(A, B, C), FromValueError>>
+    where
+        A: FromValueRef<'a>,
+        B: FromValueRef<'a>,
+        C: FromValueRef<'a>,
+    {
+        if self.len() < 3 {
+            return None;
+        }
+        Some(
+            self.column_ref(0)
+                .and_then(|a| Ok((a, self.column_ref(1)?, self.column_ref(2)?))),
+        )
+    }
+
+    /// Same as `Row::to_tuple_ref` but for the first four values.
+    pub fn to_tuple4_ref<'a, A, B, C, D>(&'a self) -> Option<Result<(A, B, C, D), FromValueError>>
+    where
+        A: FromValueRef<'a>,
+        B: FromValueRef<'a>,
+        C: FromValueRef<'a>,
+        D: FromValueRef<'a>,
+    {
+        if self.len() < 4 {
+            return None;
+        }
+        Some(self.column_ref(0).and_then(|a| {
+            Ok((
+                a,
+                self.column_ref(1)?,
+                self.column_ref(2)?,
+                self.column_ref(3)?,
+            ))
+        }))
+    }
+
+    /// Will copy every value of this row and convert it to `T`.
+    ///
+    /// Useful for homogeneous rows (e.g. numeric matrices). On error returns index of the first
+    /// column that was taken (`RowError::ColumnMissing`) or couldn't be converted
+    /// (`RowError::FromValueError`).
+    ///
+    /// The error is `RowError` rather than a bare `FromValueError`, because a taken column has no
+    /// value to put into `FromValueError` and would otherwise be indistinguishable from `NULL`.
+    pub fn try_get_all<T>(&self) -> Result<Vec<T>, (usize, RowError)>
+    where
+        T: FromValue,
+    {
+        self.values
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                let value = value.as_ref().ok_or((idx, RowError::ColumnMissing))?;
+                from_value_opt::<T>(value.clone()).map_err(|e| (idx, RowError::FromValueError(e)))
+            })
+            .collect()
+    }
+
+    /// Will copy value at index `index` and convert it to `Presence<T>`.
+    ///
+    /// Distinguishes missing or taken column (`Absent`), `NULL`, convertible value (`Present`)
+    /// and inconvertible value (`Invalid`).
+    pub fn presence<T, I>(&self, index: I) -> Presence<T>
+    where
+        T: FromValue,
+        I: ColumnIndex,
+    {
+        presence(
+            index
+                .idx(&self.columns)
+                .and_then(|idx| self.values.get(idx))
+                .and_then(|x| x.clone()),
+        )
+    }
+
+    /// Will decode value at index `index` using a decoder from `registry`.
+    ///
+    /// Returns `None` if the column doesn't exist or was taken, if there is no decoder
+    /// registered for the column or if the decoder fails.
+    pub fn get_dynamic<I>(&self, index: I, registry: &ConversionRegistry) -> Option<Box<dyn Any>>
+    where
+        I: ColumnIndex,
+    {
+        let idx = index.idx(&self.columns)?;
+        let value = self.values.get(idx)?.as_ref()?;
+        let decoder = registry.decoder(&self.columns[idx])?;
+        decoder(value).ok()
+    }
+
+    /// Returns a reference to the value of a column named `name`, computing it via `f` if absent.
+    ///
+    /// If there is no such column, then a new column (of `MYSQL_TYPE_NULL` type) is appended
+    /// to this row. Columns are cloned on write, so other rows sharing the same columns are not
+    /// affected. If the column exists but its value was taken, then the value is computed via `f`
+    /// and placed back.
+    pub fn get_or_insert_with<F>(&mut self, name: &str, f: F) -> &Value
+    where
+        F: FnOnce() -> Value,
+    {
+        let idx = match name.idx(&self.columns) {
+            Some(idx) => idx,
+            None => {
+                Arc::make_mut(&mut self.columns)
+                    .push(Column::new(name.as_bytes(), ColumnType::MYSQL_TYPE_NULL));
+                self.values.push(None);
+                self.values.len() - 1
+            }
+        };
+        self.values[idx].get_or_insert_with(f)
+    }
+
     /// Will take value of a column with index `index` if it exists and wasn't taken earlier then
     /// will converts it to `T`.
     pub fn take<T, I>(&mut self, index: I) -> Option<T>
@@ -136,16 +922,286 @@ impl Row {
             .and_then(|x| Some(from_value_opt::<T>(x)))
     }
 
-    /// Unwraps values of a row.
+    /// Takes all values that weren't taken earlier, leaving every column of this row taken.
     ///
-    /// # Panics
-    ///
-    /// Panics if any of columns was taken by `take` method.
-    pub fn unwrap(self) -> Vec<Value> {
-        self.values
-            .into_iter()
-            .map(|x| x.expect("Can't unwrap row if some of columns was taken"))
-            .collect()
+    /// Unlike `Row::unwrap`, it never panics and keeps positions: columns taken earlier
+    /// are `None`.
+    pub fn take_remaining(&mut self) -> Vec<Option<Value>> {
+        self.values.iter_mut().map(Option::take).collect()
+    }
+
+    /// Converts this row into a tuple (or any other `FromRow` implementor).
+    ///
+    /// Convenience wrapper around `FromRow::from_row_opt`. Fails if the tuple arity doesn't
+    /// match the row length or if any value is inconvertible; in both cases the original row
+    /// is returned inside `FromRowError`.
+    pub fn try_into_tuple<T: FromRow>(self) -> Result<T, FromRowError> {
+        T::from_row_opt(self)
+    }
+
+    /// Builds `T` by passing this row to `f` that `take`s columns it needs.
+    ///
+    /// Useful for manual conversions that don't fit `FromRow`. If `f` fails, then the row is
+    /// restored to its original state (including columns taken by `f`) and returned along with
+    /// the error. Values are cloned before calling `f` to make this possible.
+    pub fn build<T, F>(mut self, f: F) -> Result<T, (FromRowError, Row)>
+    where
+        F: FnOnce(&mut Row) -> Result<T, FromRowError>,
+    {
+        let original = self.values.clone();
+        match f(&mut self) {
+            Ok(output) => Ok(output),
+            Err(err) => {
+                self.values = original;
+                Err((err, self))
+            }
+        }
+    }
+
+    /// Will take raw bytes of columns with indices `indices` in one call.
+    ///
+    /// Returned vector is aligned with `indices`. Slot is `None` if the index is out of range,
+    /// if the column was already taken or if the value is not `Value::Bytes` (such value is not
+    /// taken and remains in the row).
+    pub fn take_bytes_columns(&mut self, indices: &[usize]) -> Vec<Option<Vec<u8>>> {
+        indices
+            .iter()
+            .map(|&idx| {
+                let slot = self.values.get_mut(idx)?;
+                match slot.take() {
+                    Some(Value::Bytes(bytes)) => Some(bytes),
+                    other => {
+                        *slot = other;
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Replaces present values with `f(column_name, value)` where it returns `Some`.
+    ///
+    /// Values for which `f` returns `None` are left intact, taken columns are skipped.
+    pub fn remap<F>(&mut self, f: F)
+    where
+        F: Fn(&str, &Value) -> Option<Value>,
+    {
+        for (column, slot) in self.columns.iter().zip(self.values.iter_mut()) {
+            if let Some(value) = slot {
+                if let Some(new_value) = f(&column.name_str(), value) {
+                    *value = new_value;
+                }
+            }
+        }
+    }
+
+    /// Returns this row with every present value replaced by `f(column, value)`.
+    ///
+    /// Unlike `Row::remap`, consumes the row and passes values by value, so `f` could reuse
+    /// them. Taken columns stay taken, columns are shared with the original row.
+    pub fn map_values<F>(self, mut f: F) -> Row
+    where
+        F: FnMut(&Column, Value) -> Value,
+    {
+        let Row { values, columns } = self;
+        let values = columns
+            .iter()
+            .zip(values)
+            .map(|(column, value)| value.map(|value| f(column, value)))
+            .collect();
+        Row { values, columns }
+    }
+
+    /// Returns a copy of this row with values of `sensitive` columns replaced by `[REDACTED]`.
+    ///
+    /// Useful for logging rows that contain personal data. Unknown names are ignored, taken
+    /// columns stay taken. See `Row::redacted_with` to use another replacement (e.g. `NULL`).
+    pub fn redacted(&self, sensitive: &[&str]) -> Row {
+        self.redacted_with(sensitive, Value::Bytes(b"[REDACTED]".to_vec()))
+    }
+
+    /// Returns a copy of this row with values of `sensitive` columns replaced by `replacement`.
+    pub fn redacted_with(&self, sensitive: &[&str], replacement: Value) -> Row {
+        let mut row = self.clone();
+        row.remap(|name, _| {
+            if sensitive.contains(&name) {
+                Some(replacement.clone())
+            } else {
+                None
+            }
+        });
+        row
+    }
+
+    /// Returns JSON object keyed by column name (useful for debugging and logging).
+    ///
+    /// Numbers are mapped to JSON numbers, `NULL` to `null`, bytes to a string (base64-encoded
+    /// if not a valid UTF-8), dates and times to strings in MySql format
+    /// (e.g. `"2019-11-27 12:30:00"`). Taken columns are omitted. If column name is duplicated,
+    /// then the first occurrence is used.
+    pub fn to_json(&self) -> Json {
+        let mut object = Map::new();
+        for (column, value) in self.columns.iter().zip(self.values.iter()) {
+            if let Some(value) = value {
+                object
+                    .entry(column.name_str().into_owned())
+                    .or_insert_with(|| value.to_json());
+            }
+        }
+        Json::Object(object)
+    }
+
+    /// Splits this row into a row of `known` columns and a JSON object of all the other columns.
+    ///
+    /// Columns of the returned row preserve their order in this row. Extra columns are rendered
+    /// as in `Row::to_json` (if extra column name is duplicated, then the first occurrence is
+    /// used). Taken columns are excluded from both.
+    pub fn extract_with_extras(&self, known: &[&str]) -> (Row, Json) {
+        let mut values = Vec::new();
+        let mut columns = Vec::new();
+        let mut extras = Map::new();
+        for (column, value) in self.columns.iter().zip(self.values.iter()) {
+            if let Some(value) = value {
+                let name = column.name_str();
+                if known.contains(&&*name) {
+                    values.push(Some(value.clone()));
+                    columns.push(column.clone());
+                } else {
+                    extras
+                        .entry(name.into_owned())
+                        .or_insert_with(|| value.to_json());
+                }
+            }
+        }
+        let row = Row {
+            values,
+            columns: Arc::new(columns),
+        };
+        (row, Json::Object(extras))
+    }
+
+    /// Returns JSON object describing columns that differ between `self` and `other`.
+    ///
+    /// Object is keyed by column name, each entry is `{"old": <value in self>, "new": <value in
+    /// other>}`. Values are compared using `Value::value_eq`, so a change of signedness alone
+    /// is not reported. Column that is missing (or taken) in one of rows has no corresponding
+    /// `old` or `new` field. If column name is duplicated, then the first occurrence is used.
+    pub fn diff_json(&self, other: &Row) -> Json {
+        let mut diff = Map::new();
+
+        let mut add_entry = |column: &Column, old: Option<&Value>, new: Option<&Value>| {
+            let changed = match (old, new) {
+                (Some(old), Some(new)) => !old.value_eq(new),
+                (None, None) => false,
+                _ => true,
+            };
+            let name = column.name_str().into_owned();
+            if changed && !diff.contains_key(&name) {
+                let mut entry = Map::new();
+                if let Some(old) = old {
+                    entry.insert("old".into(), old.to_json());
+                }
+                if let Some(new) = new {
+                    entry.insert("new".into(), new.to_json());
+                }
+                diff.insert(name, Json::Object(entry));
+            }
+        };
+
+        for (idx, column) in self.columns.iter().enumerate() {
+            let new = column
+                .name_str()
+                .as_ref()
+                .idx(&other.columns)
+                .and_then(|idx| other.as_ref(idx));
+            add_entry(column, self.as_ref(idx), new);
+        }
+        for (idx, column) in other.columns.iter().enumerate() {
+            if column.name_str().as_ref().idx(&self.columns).is_none() {
+                add_entry(column, None, other.as_ref(idx));
+            }
+        }
+
+        Json::Object(diff)
+    }
+
+    /// Returns a fast fingerprint of values of this row (column metadata is ignored).
+    ///
+    /// Rows with equal values in the same order have equal checksums, so it could be used
+    /// to cheaply detect whether a row changed between fetches. Taken columns contribute
+    /// a sentinel, floats are hashed by their bit pattern.
+    pub fn values_checksum(&self) -> u64 {
+        let mut hasher = XxHash64::with_seed(0);
+        for value in self.values.iter() {
+            match value {
+                Some(value) => {
+                    hasher.write_u8(1);
+                    value.hash_into(&mut hasher);
+                }
+                None => hasher.write_u8(0),
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Returns a fingerprint of the schema of this row (values are ignored).
+    ///
+    /// Hashes name and type of each column in order, so rows of the same query shape have equal
+    /// fingerprints regardless of their values and taken columns. Fingerprint is stable across
+    /// runs, so it could be used as a cache key (e.g. for query plans).
+    pub fn schema_fingerprint(&self) -> u64 {
+        let mut hasher = XxHash64::with_seed(0);
+        for column in self.columns.iter() {
+            let name = column.name_ref();
+            hasher.write_u64(name.len() as u64);
+            hasher.write(name);
+            hasher.write_u8(column.column_type() as u8);
+        }
+        hasher.finish()
+    }
+
+    /// Unwraps values of a row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of columns was taken by `take` method.
+    pub fn unwrap(self) -> Vec<Value> {
+        self.values
+            .into_iter()
+            .map(|x| x.expect("Can't unwrap row if some of columns was taken"))
+            .collect()
+    }
+
+    /// Converts this row into an immutable, cheaply-cloneable `FrozenRow`.
+    pub fn freeze(self) -> FrozenRow {
+        FrozenRow::from(self)
+    }
+
+    /// Unwraps values of a row, including taken columns (as `None`).
+    ///
+    /// Unlike `Row::unwrap` it never panics and preserves positions of taken columns.
+    pub fn into_option_values(self) -> Vec<Option<Value>> {
+        self.values
+    }
+
+    /// Returns values of a row, including taken columns (as `None`), without consuming it.
+    pub fn values(&self) -> &[Option<Value>] {
+        &self.values
+    }
+
+    /// Returns a copy of values of a row, including taken columns (as `None`).
+    ///
+    /// Unlike `Row::unwrap` it neither consumes the row nor panics.
+    pub fn to_values(&self) -> Vec<Option<Value>> {
+        self.values.clone()
+    }
+
+    /// Shrinks capacity of present values (see `Value::shrink_to_fit`) and of the row itself.
+    pub fn shrink_to_fit(&mut self) {
+        for value in self.values.iter_mut().flatten() {
+            value.shrink_to_fit();
+        }
+        self.values.shrink_to_fit();
     }
 
     #[doc(hidden)]
@@ -154,6 +1210,19 @@ impl Row {
     }
 }
 
+/// Consuming iterator over columns of a row and their values (`None` for taken columns).
+///
+/// Columns are cloned only if they are shared with other rows.
+impl IntoIterator for Row {
+    type Item = (Column, Option<Value>);
+    type IntoIter = Zip<vec::IntoIter<Column>, vec::IntoIter<Option<Value>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let columns = Arc::try_unwrap(self.columns).unwrap_or_else(|columns| (*columns).clone());
+        columns.into_iter().zip(self.values)
+    }
+}
+
 impl Index<usize> for Row {
     type Output = Value;
 
@@ -200,3 +1269,1442 @@ impl<'a> ColumnIndex for &'a str {
         None
     }
 }
+
+/// `(table, column)` pair, where `table` is matched against the table field of a column packet
+/// (i.e. table alias if any). Useful to disambiguate same-named columns of a joined row.
+impl ColumnIndex for (&str, &str) {
+    fn idx(&self, columns: &[Column]) -> Option<usize> {
+        for (i, c) in columns.iter().enumerate() {
+            if c.table_ref() == self.0.as_bytes() && c.name_ref() == self.1.as_bytes() {
+                return Some(i);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::test_column;
+
+    fn col(name: &str, ty: ColumnType) -> Column {
+        table_col(name, name, ty)
+    }
+
+    fn sized_col(name: &str, ty: ColumnType, length: u32) -> Column {
+        test_column("t", name, ty, length, ColumnFlags::empty(), None)
+    }
+
+    fn table_col(table: &str, name: &str, ty: ColumnType) -> Column {
+        test_column(table, name, ty, u32::MAX, ColumnFlags::empty(), None)
+    }
+
+    #[test]
+    fn should_fall_back_to_column_default() {
+        let with_default = test_column(
+            "t",
+            "status",
+            ColumnType::MYSQL_TYPE_LONG,
+            u32::MAX,
+            ColumnFlags::empty(),
+            Some(b"42"),
+        );
+        assert_eq!(with_default.default_value_ref(), Some(&b"42"[..]));
+        assert_eq!(
+            with_default.with_name(b"s").default_value_ref(),
+            Some(&b"42"[..])
+        );
+
+        let without_default = col("other", ColumnType::MYSQL_TYPE_LONG);
+        assert_eq!(without_default.default_value_ref(), None);
+
+        let row = new_row(
+            vec![Value::NULL, Value::NULL],
+            Arc::new(vec![with_default.clone(), without_default]),
+        );
+        assert_eq!(row.get_or_column_default::<i32, _>("status"), Some(42));
+        assert_eq!(row.get_or_column_default::<i32, _>("other"), None);
+        assert_eq!(row.get_or_column_default::<i32, _>("missing"), None);
+
+        let row = new_row(vec![Value::Int(7)], Arc::new(vec![with_default]));
+        assert_eq!(row.get_or_column_default::<i32, _>(0), Some(7));
+    }
+
+    #[test]
+    fn should_respect_column_sign() {
+        let unsigned = test_column(
+            "t",
+            "u",
+            ColumnType::MYSQL_TYPE_TINY,
+            u32::MAX,
+            ColumnFlags::UNSIGNED_FLAG,
+            None,
+        );
+        let signed = col("s", ColumnType::MYSQL_TYPE_TINY);
+
+        let row = new_row(
+            vec![Value::Int(-1), Value::Int(-1)],
+            Arc::new(vec![unsigned.clone(), signed]),
+        );
+        assert_eq!(row.get_respecting_sign("u"), Some(255));
+        assert_eq!(row.get_respecting_sign("s"), Some(-1));
+        assert_eq!(row.get_respecting_sign("missing"), None);
+
+        let row = new_row(vec![Value::Int(100)], Arc::new(vec![unsigned]));
+        assert_eq!(row.get_respecting_sign(0), Some(100));
+
+        let unsigned_decimal = test_column(
+            "t",
+            "d",
+            ColumnType::MYSQL_TYPE_NEWDECIMAL,
+            u32::MAX,
+            ColumnFlags::UNSIGNED_FLAG,
+            None,
+        );
+        let row = new_row(vec![Value::from("-1")], Arc::new(vec![unsigned_decimal]));
+        assert_eq!(row.get_respecting_sign("d"), Some(-1));
+    }
+
+    #[test]
+    fn should_borrow_tuple() {
+        let columns = Arc::new(vec![
+            col("id", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+        ]);
+        let mut row = new_row(vec![Value::Int(42), Value::from("foo")], columns);
+
+        let (id, name): (i64, &str) = row.to_tuple_ref().unwrap().unwrap();
+        assert_eq!(id, 42);
+        assert_eq!(name, "foo");
+        assert!(row.to_tuple_ref::<i64, i64>().unwrap().is_err());
+        assert!(row.to_tuple3_ref::<i64, &str, &str>().is_none());
+        assert!(row.to_tuple4_ref::<i64, &str, &str, &str>().is_none());
+
+        row.take::<String, _>("name");
+        let err = row.to_tuple_ref::<i64, &str>().unwrap().unwrap_err();
+        assert_eq!(err.0, Value::NULL);
+    }
+
+    #[cfg(feature = "encoding_rs")]
+    #[test]
+    fn should_decode_with_charset() {
+        let columns = Arc::new(vec![
+            col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("id", ColumnType::MYSQL_TYPE_LONG),
+        ]);
+        let row = new_row(
+            vec![Value::Bytes(b"caf\xe9 \x80".to_vec()), Value::Int(1)],
+            columns,
+        );
+        assert_eq!(
+            row.get_with_charset("name", Charset::Latin1).unwrap(),
+            "caf\u{e9} \u{20ac}"
+        );
+        assert_eq!(
+            row.get_with_charset("name", Charset::Utf8).unwrap(),
+            "caf\u{fffd} \u{fffd}"
+        );
+        assert_eq!(row.get_with_charset("id", Charset::Latin1), None);
+        assert_eq!(row.get_with_charset("missing", Charset::Latin1), None);
+    }
+
+    #[test]
+    fn should_get_presence() {
+        let columns = Arc::new(vec![
+            col("a", ColumnType::MYSQL_TYPE_LONG),
+            col("b", ColumnType::MYSQL_TYPE_LONG),
+        ]);
+        let row = new_row(vec![Value::Int(1), Value::NULL], columns);
+        assert!(matches!(row.presence::<i32, _>("a"), Presence::Present(1)));
+        assert!(matches!(row.presence::<i32, _>("b"), Presence::Null));
+        assert!(matches!(row.presence::<i32, _>("c"), Presence::Absent));
+        assert!(matches!(
+            row.presence::<bool, _>(0),
+            Presence::Present(true)
+        ));
+    }
+
+    #[test]
+    fn should_transpose_rows() {
+        let columns = Arc::new(vec![
+            col("id", ColumnType::MYSQL_TYPE_LONG),
+            col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+        ]);
+        let mut rows = vec![
+            new_row(vec![Value::Int(1), Value::from("foo")], columns.clone()),
+            new_row(vec![Value::Int(2), Value::NULL], columns.clone()),
+            new_row(
+                vec![Value::Int(3), Value::from("baz")],
+                Arc::new((*columns).clone()),
+            ),
+        ];
+        rows[1].take::<Value, _>("name");
+
+        assert_eq!(
+            transpose(&rows).unwrap(),
+            vec![
+                (
+                    "id".to_string(),
+                    vec![
+                        Some(Value::Int(1)),
+                        Some(Value::Int(2)),
+                        Some(Value::Int(3))
+                    ]
+                ),
+                (
+                    "name".to_string(),
+                    vec![Some(Value::from("foo")), None, Some(Value::from("baz"))]
+                ),
+            ]
+        );
+        assert_eq!(transpose(&[]).unwrap(), vec![]);
+
+        rows.push(new_row(
+            vec![Value::Int(4), Value::Int(5)],
+            Arc::new(vec![
+                col("id", ColumnType::MYSQL_TYPE_LONG),
+                col("name", ColumnType::MYSQL_TYPE_LONG),
+            ]),
+        ));
+        assert_eq!(
+            transpose(&rows).unwrap_err(),
+            TransposeError::SchemaMismatch { row: 3 }
+        );
+    }
+
+    #[test]
+    fn should_count_present_values() {
+        let columns = Arc::new(vec![
+            col("a", ColumnType::MYSQL_TYPE_LONG),
+            col("b", ColumnType::MYSQL_TYPE_LONG),
+            col("c", ColumnType::MYSQL_TYPE_LONG),
+            col("d", ColumnType::MYSQL_TYPE_LONG),
+        ]);
+        let mut row = new_row(
+            vec![Value::Int(1), Value::NULL, Value::Int(3), Value::NULL],
+            columns,
+        );
+        assert_eq!(row.count_present(), 4);
+        assert_eq!(row.count_non_null(), 2);
+
+        row.take::<i32, _>("c");
+        row.take::<Value, _>("d");
+        assert_eq!(row.count_present(), 2);
+        assert_eq!(row.count_non_null(), 1);
+
+        row.take::<i32, _>("a");
+        row.take::<Value, _>("b");
+        assert_eq!(row.count_present(), 0);
+        assert_eq!(row.count_non_null(), 0);
+    }
+
+    #[test]
+    fn should_redact_columns() {
+        let columns = Arc::new(vec![
+            col("id", ColumnType::MYSQL_TYPE_LONG),
+            col("email", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("phone", ColumnType::MYSQL_TYPE_VAR_STRING),
+        ]);
+        let mut row = new_row(
+            vec![
+                Value::Int(1),
+                Value::from("foo@example.com"),
+                Value::from("555-0100"),
+            ],
+            columns,
+        );
+        row.take::<String, _>("phone");
+
+        let redacted = row.redacted(&["email", "phone", "missing"]);
+        assert_eq!(redacted[0], Value::Int(1));
+        assert_eq!(redacted["email"], Value::from("[REDACTED]"));
+        assert_eq!(redacted.as_ref(2), None);
+        assert_eq!(row["email"], Value::from("foo@example.com"));
+
+        let redacted = row.redacted_with(&["email"], Value::NULL);
+        assert_eq!(redacted["email"], Value::NULL);
+        assert_eq!(redacted[0], Value::Int(1));
+    }
+
+    #[test]
+    fn should_coalesce_columns() {
+        let columns = Arc::new(vec![
+            col("a", ColumnType::MYSQL_TYPE_LONG),
+            col("b", ColumnType::MYSQL_TYPE_LONG),
+            col("c", ColumnType::MYSQL_TYPE_LONG),
+        ]);
+        let mut row = new_row(
+            vec![Value::NULL, Value::Int(2), Value::Int(3)],
+            columns.clone(),
+        );
+        assert_eq!(row.coalesce::<i32>(&[0, 1, 2]), Some(2));
+        assert_eq!(row.coalesce::<i32>(&[2, 1]), Some(3));
+        row.take::<i32, _>(1);
+        assert_eq!(row.coalesce::<i32>(&[0, 1, 2]), Some(3));
+        assert_eq!(row.coalesce::<i32>(&[0, 1, 5]), None);
+        assert_eq!(row.coalesce::<i32>(&[]), None);
+
+        let row = new_row(
+            vec![Value::NULL, Value::NULL, Value::Int(3)],
+            columns.clone(),
+        );
+        assert_eq!(row.coalesce::<i32>(&[0, 1, 2]), Some(3));
+        assert_eq!(row.coalesce::<i32>(&[0, 1]), None);
+
+        let row = new_row(
+            vec![Value::NULL, Value::from("foo"), Value::Int(3)],
+            columns,
+        );
+        assert_eq!(row.coalesce::<i32>(&[0, 1, 2]), None);
+    }
+
+    #[test]
+    fn should_get_all_values() {
+        let columns = Arc::new(vec![
+            col("a", ColumnType::MYSQL_TYPE_LONG),
+            col("b", ColumnType::MYSQL_TYPE_LONG),
+            col("c", ColumnType::MYSQL_TYPE_LONG),
+        ]);
+        let row = new_row(
+            vec![Value::Int(1), Value::from("2"), Value::UInt(3)],
+            columns.clone(),
+        );
+        assert_eq!(row.try_get_all::<i32>().unwrap(), vec![1, 2, 3]);
+
+        let row = new_row(
+            vec![Value::Int(1), Value::from("foo"), Value::Int(3)],
+            columns.clone(),
+        );
+        match row.try_get_all::<i32>() {
+            Err((1, RowError::FromValueError(err))) => assert_eq!(err.0, Value::from("foo")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let mut row = new_row(vec![Value::Int(1), Value::Int(2), Value::Int(3)], columns);
+        row.take::<i32, _>(2);
+        match row.try_get_all::<i32>() {
+            Err((2, RowError::ColumnMissing)) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let row = new_row(vec![], Arc::new(vec![]));
+        assert_eq!(row.try_get_all::<i32>().unwrap(), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn should_take_bytes_columns() {
+        let columns = vec![
+            col("a", ColumnType::MYSQL_TYPE_BLOB),
+            col("b", ColumnType::MYSQL_TYPE_BLOB),
+            col("c", ColumnType::MYSQL_TYPE_BLOB),
+            col("d", ColumnType::MYSQL_TYPE_LONGLONG),
+        ];
+        let mut row = new_row(
+            vec![
+                Value::Bytes(b"foo".to_vec()),
+                Value::Bytes(b"bar".to_vec()),
+                Value::Bytes(b"baz".to_vec()),
+                Value::Int(42),
+            ],
+            Arc::new(columns),
+        );
+
+        let taken = row.take_bytes_columns(&[0, 2]);
+        assert_eq!(taken, vec![Some(b"foo".to_vec()), Some(b"baz".to_vec())]);
+        assert_eq!(row.as_ref(0), None);
+        assert_eq!(row.as_ref(1), Some(&Value::Bytes(b"bar".to_vec())));
+        assert_eq!(row.as_ref(2), None);
+
+        let taken = row.take_bytes_columns(&[0, 3, 4]);
+        assert_eq!(taken, vec![None, None, None]);
+        assert_eq!(row.as_ref(3), Some(&Value::Int(42)));
+    }
+
+    #[test]
+    fn should_index_by_table_and_column() {
+        let columns = vec![
+            table_col("users", "id", ColumnType::MYSQL_TYPE_LONGLONG),
+            table_col("users", "name", ColumnType::MYSQL_TYPE_VAR_STRING),
+            table_col("posts", "id", ColumnType::MYSQL_TYPE_LONGLONG),
+        ];
+        let row = new_row(
+            vec![Value::Int(1), Value::from("foo"), Value::Int(2)],
+            Arc::new(columns),
+        );
+
+        assert_eq!(row.get::<i64, _>(("users", "id")), Some(1));
+        assert_eq!(row.get::<i64, _>(("posts", "id")), Some(2));
+        assert_eq!(row.get::<String, _>(("users", "name")), Some("foo".into()));
+        assert_eq!(row.get::<i64, _>(("Users", "id")), None);
+        assert_eq!(row.get::<i64, _>(("comments", "id")), None);
+        assert_eq!(row.get::<i64, _>(("posts", "name")), None);
+    }
+
+    #[test]
+    fn should_get_nullable() {
+        let columns = vec![
+            col("a", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("b", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("c", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("d", ColumnType::MYSQL_TYPE_LONGLONG),
+        ];
+        let mut row = new_row(
+            vec![
+                Value::Int(42),
+                Value::NULL,
+                Value::from("foo"),
+                Value::Int(0),
+            ],
+            Arc::new(columns),
+        );
+        row.take::<i64, _>("d");
+
+        assert_eq!(row.get_nullable::<i64, _>("a").unwrap(), Some(42));
+        assert_eq!(row.get_nullable::<i64, _>("b").unwrap(), None);
+        match row.get_nullable::<i64, _>("c") {
+            Err(RowError::FromValueError(err)) => assert_eq!(err.0, Value::from("foo")),
+            x => panic!("unexpected result {:?}", x),
+        }
+        match row.get_nullable::<i64, _>("d") {
+            Err(RowError::ColumnMissing) => (),
+            x => panic!("unexpected result {:?}", x),
+        }
+        match row.get_nullable::<i64, _>("e") {
+            Err(RowError::ColumnMissing) => (),
+            x => panic!("unexpected result {:?}", x),
+        }
+    }
+
+    #[test]
+    fn should_compute_values_checksum() {
+        let columns = Arc::new(vec![
+            col("a", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("b", ColumnType::MYSQL_TYPE_VAR_STRING),
+        ]);
+        let other_columns = Arc::new(vec![
+            col("c", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("d", ColumnType::MYSQL_TYPE_VAR_STRING),
+        ]);
+        let row = new_row(vec![Value::Int(1), Value::from("foo")], columns.clone());
+        let same = new_row(vec![Value::Int(1), Value::from("foo")], other_columns);
+        let changed = new_row(vec![Value::Int(1), Value::from("bar")], columns.clone());
+        let uint = new_row(vec![Value::UInt(1), Value::from("foo")], columns);
+
+        assert_eq!(row.values_checksum(), same.values_checksum());
+        assert_ne!(row.values_checksum(), changed.values_checksum());
+        assert_ne!(row.values_checksum(), uint.values_checksum());
+
+        let mut taken = row.clone();
+        taken.take::<Value, _>(1);
+        assert_ne!(row.values_checksum(), taken.values_checksum());
+    }
+
+    #[test]
+    fn should_get_or_insert_derived_column() {
+        let columns = Arc::new(vec![col("a", ColumnType::MYSQL_TYPE_LONGLONG)]);
+        let mut row = new_row(vec![Value::Int(21)], columns.clone());
+        let mut calls = 0;
+
+        let value = row.get_or_insert_with("double", || {
+            calls += 1;
+            Value::Int(42)
+        });
+        assert_eq!(value, &Value::Int(42));
+        let value = row.get_or_insert_with("double", || {
+            calls += 1;
+            Value::Int(0)
+        });
+        assert_eq!(value, &Value::Int(42));
+        assert_eq!(calls, 1);
+
+        assert_eq!(row.len(), 2);
+        assert_eq!(row.get::<i64, _>("double"), Some(42));
+        assert_eq!(row.columns_ref()[1].name_str(), "double");
+        assert_eq!(columns.len(), 1);
+
+        assert_eq!(
+            row.get_or_insert_with("a", || Value::Int(0)),
+            &Value::Int(21)
+        );
+        row.take::<i64, _>("a");
+        assert_eq!(
+            row.get_or_insert_with("a", || Value::Int(0)),
+            &Value::Int(0)
+        );
+    }
+
+    #[test]
+    fn should_get_dynamic() {
+        #[derive(Debug, PartialEq)]
+        struct Point(i64, i64);
+
+        let mut registry = ConversionRegistry::new();
+        registry
+            .register_name("pos_*", |value| match value {
+                Value::Bytes(bytes) => {
+                    let s = String::from_utf8_lossy(bytes);
+                    let mut parts = s.split(',').map(|x| x.parse::<i64>());
+                    match (parts.next(), parts.next(), parts.next()) {
+                        (Some(Ok(x)), Some(Ok(y)), None) => {
+                            Ok(Box::new(Point(x, y)) as Box<dyn Any>)
+                        }
+                        _ => Err(FromValueError(value.clone())),
+                    }
+                }
+                _ => Err(FromValueError(value.clone())),
+            })
+            .register_type(ColumnType::MYSQL_TYPE_LONGLONG, |value| {
+                from_value_opt::<i64>(value.clone()).map(|x| Box::new(x) as Box<dyn Any>)
+            });
+
+        let columns = vec![
+            col("pos_start", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("pos_end", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("id", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+        ];
+        let row = new_row(
+            vec![
+                Value::from("1,2"),
+                Value::from("foo"),
+                Value::Int(42),
+                Value::from("bar"),
+            ],
+            Arc::new(columns),
+        );
+
+        let point = row.get_dynamic("pos_start", &registry).unwrap();
+        assert_eq!(point.downcast_ref::<Point>(), Some(&Point(1, 2)));
+        let id = row.get_dynamic(2, &registry).unwrap();
+        assert_eq!(id.downcast_ref::<i64>(), Some(&42));
+        assert!(row.get_dynamic("pos_end", &registry).is_none());
+        assert!(row.get_dynamic("name", &registry).is_none());
+        assert!(row.get_dynamic("missing", &registry).is_none());
+
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+        assert_send_sync(&registry);
+    }
+
+    #[test]
+    fn should_borrow_consecutive_values() {
+        let columns = vec![
+            col("a", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("b", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("c", ColumnType::MYSQL_TYPE_NULL),
+            col("d", ColumnType::MYSQL_TYPE_LONGLONG),
+        ];
+        let mut row = new_row(
+            vec![
+                Value::Int(1),
+                Value::from("foo"),
+                Value::NULL,
+                Value::Int(2),
+            ],
+            Arc::new(columns),
+        );
+
+        let [a, b, c] = row.as_refs::<3>(0).unwrap();
+        assert_eq!(a, &Value::Int(1));
+        assert_eq!(b, &Value::from("foo"));
+        assert_eq!(c, &Value::NULL);
+        assert_eq!(row.as_refs::<0>(4), Some([]));
+        assert_eq!(row.as_refs::<2>(3), None);
+        assert_eq!(row.as_refs::<2>(usize::MAX), None);
+
+        row.take::<String, _>(1);
+        assert_eq!(row.as_refs::<3>(0), None);
+        assert_eq!(row.as_refs::<2>(2), Some([&Value::NULL, &Value::Int(2)]));
+    }
+
+    #[test]
+    fn should_validate_row_schema() {
+        let columns = vec![
+            col("id", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("price", ColumnType::MYSQL_TYPE_NEWDECIMAL),
+        ];
+        let row = new_row(
+            vec![Value::Int(1), Value::from("foo"), Value::from("1.50")],
+            Arc::new(columns),
+        );
+
+        assert_eq!(
+            row.validate(&[
+                ("id", ColumnType::MYSQL_TYPE_LONGLONG),
+                ("name", ColumnType::MYSQL_TYPE_VARCHAR),
+                ("price", ColumnType::MYSQL_TYPE_DECIMAL),
+            ]),
+            Ok(())
+        );
+        assert_eq!(
+            row.validate(&[
+                ("id", ColumnType::MYSQL_TYPE_LONGLONG),
+                ("name", ColumnType::MYSQL_TYPE_BLOB),
+                ("price", ColumnType::MYSQL_TYPE_NEWDECIMAL),
+            ]),
+            Err(SchemaError::ColumnType {
+                position: 1,
+                name: "name".into(),
+                expected: ColumnType::MYSQL_TYPE_BLOB,
+                actual: ColumnType::MYSQL_TYPE_VAR_STRING,
+            })
+        );
+        assert_eq!(
+            row.validate(&[
+                ("id", ColumnType::MYSQL_TYPE_LONGLONG),
+                ("title", ColumnType::MYSQL_TYPE_VAR_STRING)
+            ]),
+            Err(SchemaError::ColumnName {
+                position: 1,
+                expected: "title".into(),
+                actual: "name".into(),
+            })
+        );
+        assert_eq!(
+            row.validate(&[("id", ColumnType::MYSQL_TYPE_LONGLONG)]),
+            Err(SchemaError::ColumnCount {
+                expected: 1,
+                actual: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn should_collect_columns() {
+        use std::collections::BTreeMap;
+
+        let columns = vec![
+            col("a", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("b", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("c", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("a", ColumnType::MYSQL_TYPE_LONGLONG),
+        ];
+        let mut row = new_row(
+            vec![
+                Value::Int(1),
+                Value::from("foo"),
+                Value::Int(3),
+                Value::Int(4),
+            ],
+            Arc::new(columns),
+        );
+        row.take::<i64, _>(2);
+
+        let map: BTreeMap<String, Value> = row.collect_columns();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["a"], Value::Int(4));
+        assert_eq!(map["b"], Value::from("foo"));
+
+        let pairs: Vec<(String, Value)> = row.collect_columns();
+        assert_eq!(
+            pairs,
+            vec![
+                ("a".into(), Value::Int(1)),
+                ("b".into(), Value::from("foo")),
+                ("a".into(), Value::Int(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_unwrap_option_values() {
+        let columns = vec![
+            col("a", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("b", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("c", ColumnType::MYSQL_TYPE_NULL),
+        ];
+        let mut row = new_row(
+            vec![Value::Int(1), Value::from("foo"), Value::NULL],
+            Arc::new(columns),
+        );
+        row.take::<String, _>(1);
+
+        assert_eq!(
+            row.into_option_values(),
+            vec![Some(Value::Int(1)), None, Some(Value::NULL)]
+        );
+    }
+
+    #[test]
+    fn should_get_enum() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        enum Size {
+            Small,
+            Medium,
+            Large,
+        }
+
+        const SIZES: &[(&str, Size)] = &[
+            ("small", Size::Small),
+            ("medium", Size::Medium),
+            ("large", Size::Large),
+        ];
+
+        let columns = vec![
+            col("a", ColumnType::MYSQL_TYPE_STRING),
+            col("b", ColumnType::MYSQL_TYPE_STRING),
+            col("c", ColumnType::MYSQL_TYPE_STRING),
+            col("d", ColumnType::MYSQL_TYPE_STRING),
+            col("e", ColumnType::MYSQL_TYPE_NULL),
+        ];
+        let row = new_row(
+            vec![
+                Value::from("small"),
+                Value::from("large"),
+                Value::from("huge"),
+                Value::from("Medium"),
+                Value::NULL,
+            ],
+            Arc::new(columns),
+        );
+
+        assert_eq!(row.get_enum("a", SIZES), Some(Size::Small));
+        assert_eq!(row.get_enum(1, SIZES), Some(Size::Large));
+        assert_eq!(row.get_enum("c", SIZES), None);
+        assert_eq!(row.get_enum("d", SIZES), None);
+        assert_eq!(row.get_enum("e", SIZES), None);
+        assert_eq!(row.get_enum("missing", SIZES), None);
+    }
+
+    #[test]
+    fn should_remap_values() {
+        let columns = vec![
+            col("id", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("status", ColumnType::MYSQL_TYPE_TINY),
+            col("prev_status", ColumnType::MYSQL_TYPE_TINY),
+            col("next_status", ColumnType::MYSQL_TYPE_TINY),
+        ];
+        let mut row = new_row(
+            vec![Value::Int(1), Value::Int(2), Value::Int(9), Value::Int(1)],
+            Arc::new(columns),
+        );
+        row.take::<i64, _>("next_status");
+
+        row.remap(|name, value| {
+            if !name.ends_with("status") {
+                return None;
+            }
+            match value {
+                Value::Int(1) => Some(Value::from("active")),
+                Value::Int(2) => Some(Value::from("archived")),
+                _ => None,
+            }
+        });
+
+        assert_eq!(row["id"], Value::Int(1));
+        assert_eq!(row["status"], Value::from("archived"));
+        assert_eq!(row["prev_status"], Value::Int(9));
+        assert_eq!(row.as_ref(3), None);
+    }
+
+    #[test]
+    fn should_convert_row_to_tuple() {
+        use crate::row::convert::{from_row, from_row_opt};
+
+        let columns = Arc::new(vec![
+            col("id", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+        ]);
+        let row = new_row(vec![Value::Int(1), Value::from("foo")], columns.clone());
+        assert_eq!(
+            from_row::<(i64, String)>(row.clone()),
+            (1, "foo".to_string())
+        );
+
+        // arity mismatch returns the row intact
+        let FromRowError(same) = from_row_opt::<(i64, String, i64)>(row.clone()).unwrap_err();
+        assert_eq!(same, row);
+        assert!(from_row_opt::<i64>(row.clone()).is_err());
+
+        // failed conversion rolls back already converted columns
+        let FromRowError(same) = from_row_opt::<(i64, i64)>(row.clone()).unwrap_err();
+        assert_eq!(same, row);
+
+        let row = new_row(vec![Value::Int(42)], Arc::new(vec![columns[0].clone()]));
+        assert_eq!(from_row::<i64>(row.clone()), 42);
+        assert_eq!(from_row::<(u8,)>(row), (42,));
+    }
+
+    #[test]
+    fn should_convert_row_to_json() {
+        let columns = Arc::new(vec![
+            col("null", ColumnType::MYSQL_TYPE_NULL),
+            col("int", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("uint", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("float", ColumnType::MYSQL_TYPE_DOUBLE),
+            col("text", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("blob", ColumnType::MYSQL_TYPE_BLOB),
+            col("date", ColumnType::MYSQL_TYPE_DATETIME),
+            col("time", ColumnType::MYSQL_TYPE_TIME),
+            col("taken", ColumnType::MYSQL_TYPE_LONG),
+        ]);
+        let mut row = new_row(
+            vec![
+                Value::NULL,
+                Value::Int(-1),
+                Value::UInt(u64::MAX),
+                Value::Float(1.5),
+                Value::from("foo"),
+                Value::Bytes(vec![0xff, 0x00]),
+                Value::Date(2019, 11, 27, 12, 30, 0, 0),
+                Value::Time(true, 1, 2, 3, 4, 5),
+                Value::Int(0),
+            ],
+            columns,
+        );
+        row.take::<i32, _>("taken");
+
+        assert_eq!(
+            row.to_json(),
+            serde_json::json!({
+                "null": null,
+                "int": -1,
+                "uint": u64::MAX,
+                "float": 1.5,
+                "text": "foo",
+                "blob": "/wA=",
+                "date": "2019-11-27 12:30:00",
+                "time": "-026:03:04.000005",
+            })
+        );
+    }
+
+    #[test]
+    fn should_extract_with_extras() {
+        let columns = Arc::new(vec![
+            col("id", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("color", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("size", ColumnType::MYSQL_TYPE_LONG),
+            col("color", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("weight", ColumnType::MYSQL_TYPE_LONG),
+        ]);
+        let mut row = new_row(
+            vec![
+                Value::Int(1),
+                Value::from("red"),
+                Value::from("foo"),
+                Value::Int(42),
+                Value::from("blue"),
+                Value::Int(7),
+            ],
+            columns,
+        );
+        row.take::<i32, _>("weight");
+
+        let (known, extras) = row.extract_with_extras(&["id", "name", "missing"]);
+        assert_eq!(known.len(), 2);
+        assert_eq!(known.get::<i64, _>("id"), Some(1));
+        assert_eq!(known.get::<String, _>("name"), Some("foo".into()));
+        assert_eq!(known.columns_ref()[1].name_str(), "name");
+        assert_eq!(extras, serde_json::json!({ "color": "red", "size": 42 }));
+    }
+
+    #[test]
+    fn should_get_bool_according_to_column_type() {
+        let columns = Arc::new(vec![
+            sized_col("flag", ColumnType::MYSQL_TYPE_TINY, 1),
+            sized_col("bit", ColumnType::MYSQL_TYPE_BIT, 1),
+            sized_col("tiny", ColumnType::MYSQL_TYPE_TINY, 4),
+        ]);
+        assert_eq!(columns[0].column_length(), 1);
+
+        let row = new_row(
+            vec![Value::Int(5), Value::Bytes(vec![1]), Value::Int(5)],
+            columns.clone(),
+        );
+        assert_eq!(row.get_bool("flag"), Some(true));
+        assert_eq!(row.get_bool("bit"), Some(true));
+        assert_eq!(row.get_bool("tiny"), None);
+
+        let row = new_row(
+            vec![Value::from("0"), Value::Bytes(vec![0]), Value::Int(1)],
+            columns.clone(),
+        );
+        assert_eq!(row.get_bool("flag"), Some(false));
+        assert_eq!(row.get_bool("bit"), Some(false));
+        assert_eq!(row.get_bool("tiny"), Some(true));
+
+        let row = new_row(vec![Value::NULL, Value::NULL, Value::Int(0)], columns);
+        assert_eq!(row.get_bool("flag"), None);
+        assert_eq!(row.get_bool("bit"), None);
+        assert_eq!(row.get_bool("tiny"), Some(false));
+        assert_eq!(row.get_bool("missing"), None);
+    }
+
+    #[test]
+    fn should_build_from_row() {
+        #[derive(Debug, PartialEq)]
+        struct User {
+            id: u64,
+            name: String,
+        }
+
+        fn build_user(row: &mut Row) -> Result<User, FromRowError> {
+            let id = row.take_opt("id");
+            let name = row.take_opt("name");
+            match (id, name) {
+                (Some(Ok(id)), Some(Ok(name))) => Ok(User { id, name }),
+                _ => Err(FromRowError(row.clone())),
+            }
+        }
+
+        let columns = Arc::new(vec![
+            col("id", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+        ]);
+        let row = new_row(vec![Value::Int(1), Value::from("foo")], columns.clone());
+        assert_eq!(
+            row.build(build_user).unwrap(),
+            User {
+                id: 1,
+                name: "foo".into()
+            }
+        );
+
+        let row = new_row(vec![Value::Int(1), Value::Bytes(vec![0xff])], columns);
+        let (_, restored) = row.clone().build(build_user).unwrap_err();
+        assert_eq!(restored, row);
+        assert_eq!(restored.count_present(), 2);
+    }
+
+    #[test]
+    fn should_find_column_index() {
+        let columns = Arc::new(vec![
+            col("id", ColumnType::MYSQL_TYPE_LONG),
+            col("Name", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("id", ColumnType::MYSQL_TYPE_LONG),
+        ]);
+        let rows = [
+            new_row(
+                vec![Value::Int(1), Value::from("foo"), Value::Int(3)],
+                columns.clone(),
+            ),
+            new_row(
+                vec![Value::Int(2), Value::from("bar"), Value::Int(4)],
+                columns,
+            ),
+        ];
+
+        let idx = rows[0].column_index("Name").unwrap();
+        assert_eq!(idx, 1);
+        let names = rows
+            .iter()
+            .map(|row| row.get::<String, _>(idx).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["foo", "bar"]);
+
+        assert_eq!(rows[0].column_index("id"), Some(0));
+        assert_eq!(rows[0].column_index("name"), None);
+        assert_eq!(rows[0].column_index("ID"), None);
+        assert_eq!(rows[0].column_index("missing"), None);
+    }
+
+    #[test]
+    fn should_iterate_over_columns_and_values() {
+        let columns = Arc::new(vec![
+            col("id", ColumnType::MYSQL_TYPE_LONG),
+            col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+        ]);
+        let mut row = new_row(vec![Value::Int(1), Value::from("foo")], columns.clone());
+        row.take::<String, _>("name");
+
+        let pairs = row
+            .iter()
+            .map(|(column, value)| (column.name_str().into_owned(), value.cloned()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            pairs,
+            vec![("id".into(), Some(Value::Int(1))), ("name".into(), None)]
+        );
+
+        let owned = row.into_iter().collect::<Vec<_>>();
+        assert_eq!(
+            owned,
+            vec![
+                (columns[0].clone(), Some(Value::Int(1))),
+                (columns[1].clone(), None)
+            ]
+        );
+    }
+
+    #[test]
+    fn should_get_nested() {
+        #[derive(Debug, PartialEq)]
+        struct User {
+            id: u64,
+            name: String,
+        }
+
+        impl FromRow for User {
+            fn from_row_opt(row: Row) -> Result<Self, FromRowError> {
+                let id = row.get_opt("id").and_then(Result::ok);
+                let name = row.get_opt("name").and_then(Result::ok);
+                match (id, name) {
+                    (Some(id), Some(name)) if row.len() == 2 => Ok(User { id, name }),
+                    _ => Err(FromRowError(row)),
+                }
+            }
+        }
+
+        let columns = vec![
+            col("id", ColumnType::MYSQL_TYPE_LONGLONG),
+            table_col("users", "user.id", ColumnType::MYSQL_TYPE_LONGLONG),
+            table_col("users", "user.name", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("users.id", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("author.id", ColumnType::MYSQL_TYPE_LONGLONG),
+        ];
+        let row = new_row(
+            vec![
+                Value::Int(1),
+                Value::Int(2),
+                Value::from("foo"),
+                Value::Int(3),
+                Value::Int(4),
+            ],
+            Arc::new(columns),
+        );
+
+        let user = row.get_nested::<User>("user").unwrap().unwrap();
+        assert_eq!(
+            user,
+            User {
+                id: 2,
+                name: "foo".into()
+            }
+        );
+        assert!(row.get_nested::<User>("author").unwrap().is_err());
+        assert!(row.get_nested::<User>("use").is_none());
+        assert!(row.get_nested::<User>("missing").is_none());
+    }
+
+    #[test]
+    fn column_with_name_should_preserve_metadata() {
+        let column = table_col("users", "user.id", ColumnType::MYSQL_TYPE_LONGLONG);
+        let renamed = column.with_name(b"id");
+        assert_eq!(renamed.name_ref(), b"id");
+        assert_eq!(renamed.org_name_ref(), column.org_name_ref());
+        assert_eq!(renamed.table_ref(), b"users");
+        assert_eq!(renamed.schema_ref(), b"db");
+        assert_eq!(renamed.column_type(), column.column_type());
+        assert_eq!(renamed.character_set(), column.character_set());
+        assert_eq!(renamed.column_length(), column.column_length());
+        assert_eq!(renamed.flags(), column.flags());
+        assert_eq!(renamed.decimals(), column.decimals());
+    }
+
+    #[test]
+    fn should_shrink_row_to_fit() {
+        let mut bytes = Vec::with_capacity(1024);
+        bytes.extend_from_slice(b"foo");
+        let columns = vec![
+            col("a", ColumnType::MYSQL_TYPE_BLOB),
+            col("b", ColumnType::MYSQL_TYPE_LONGLONG),
+        ];
+        let mut row = new_row(vec![Value::Bytes(bytes), Value::Int(1)], Arc::new(columns));
+        row.values.reserve(64);
+
+        row.shrink_to_fit();
+        assert!(row.values.capacity() < 64);
+        match row[0] {
+            Value::Bytes(ref bytes) => {
+                assert_eq!(bytes, b"foo");
+                assert!(bytes.capacity() < 1024);
+            }
+            _ => unreachable!(),
+        }
+        assert_eq!(row[1], Value::Int(1));
+    }
+
+    #[test]
+    fn should_diff_rows_as_json() {
+        let old = new_row(
+            vec![
+                Value::Int(1),
+                Value::from("foo"),
+                Value::NULL,
+                Value::Int(5),
+                Value::Int(7),
+            ],
+            Arc::new(vec![
+                col("id", ColumnType::MYSQL_TYPE_LONGLONG),
+                col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+                col("email", ColumnType::MYSQL_TYPE_VAR_STRING),
+                col("score", ColumnType::MYSQL_TYPE_LONGLONG),
+                col("removed", ColumnType::MYSQL_TYPE_LONGLONG),
+            ]),
+        );
+        let new = new_row(
+            vec![
+                Value::UInt(1),
+                Value::from("bar"),
+                Value::from("bar@example.com"),
+                Value::NULL,
+                Value::Int(8),
+            ],
+            Arc::new(vec![
+                col("id", ColumnType::MYSQL_TYPE_LONGLONG),
+                col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+                col("email", ColumnType::MYSQL_TYPE_VAR_STRING),
+                col("score", ColumnType::MYSQL_TYPE_LONGLONG),
+                col("added", ColumnType::MYSQL_TYPE_LONGLONG),
+            ]),
+        );
+
+        assert_eq!(
+            old.diff_json(&new),
+            serde_json::json!({
+                "name": { "old": "foo", "new": "bar" },
+                "email": { "old": null, "new": "bar@example.com" },
+                "score": { "old": 5, "new": null },
+                "removed": { "old": 7 },
+                "added": { "new": 8 },
+            })
+        );
+        assert_eq!(old.diff_json(&old), serde_json::json!({}));
+    }
+
+    #[test]
+    fn should_display_row_on_single_line() {
+        let columns = Arc::new(vec![
+            col("null", ColumnType::MYSQL_TYPE_NULL),
+            col("int", ColumnType::MYSQL_TYPE_LONGLONG),
+            col("float", ColumnType::MYSQL_TYPE_DOUBLE),
+            col("text", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("blob", ColumnType::MYSQL_TYPE_BLOB),
+            col("date", ColumnType::MYSQL_TYPE_DATETIME),
+            col("taken", ColumnType::MYSQL_TYPE_LONG),
+        ]);
+        let mut row = new_row(
+            vec![
+                Value::NULL,
+                Value::Int(-1),
+                Value::Float(1.5),
+                Value::from("foo bar"),
+                Value::Bytes(vec![0xff, 0x00, 0x1a]),
+                Value::Date(2019, 11, 27, 12, 30, 0, 0),
+                Value::Int(0),
+            ],
+            columns,
+        );
+        row.take::<i32, _>("taken");
+
+        assert_eq!(
+            row.to_string(),
+            "null=NULL, int=-1, float=1.5, text=foo bar, blob=0xFF001A, \
+             date='2019-11-27 12:30:00', taken=<taken>"
+        );
+    }
+
+    #[test]
+    fn should_lookup_column_by_name() {
+        let columns = Arc::new(vec![
+            col("id", ColumnType::MYSQL_TYPE_LONG),
+            col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("taken", ColumnType::MYSQL_TYPE_LONG),
+        ]);
+        let mut row = new_row(
+            vec![Value::Int(1), Value::from("foo"), Value::Int(2)],
+            columns,
+        );
+        row.take::<i32, _>("taken");
+
+        assert_eq!(row.lookup::<i32>("id").unwrap(), 1);
+        assert_eq!(row.lookup::<String>("name").unwrap(), "foo");
+        assert!(matches!(
+            row.lookup::<i32>("missing"),
+            Err(RowLookupError::NoSuchColumn)
+        ));
+        assert!(matches!(
+            row.lookup::<i32>("taken"),
+            Err(RowLookupError::Taken)
+        ));
+        match row.lookup::<i32>("name") {
+            Err(RowLookupError::Conversion(err)) => assert_eq!(err.0, Value::from("foo")),
+            _ => panic!("expected conversion error"),
+        }
+    }
+
+    #[test]
+    fn should_fingerprint_schema() {
+        let columns = Arc::new(vec![
+            col("id", ColumnType::MYSQL_TYPE_LONG),
+            col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+        ]);
+        let row = new_row(vec![Value::Int(1), Value::from("foo")], columns.clone());
+        let mut other = new_row(vec![Value::Int(2), Value::NULL], columns);
+        other.take::<i32, _>("id");
+        assert_eq!(row.schema_fingerprint(), other.schema_fingerprint());
+
+        let renamed = new_row(
+            vec![Value::Int(1), Value::from("foo")],
+            Arc::new(vec![
+                col("id", ColumnType::MYSQL_TYPE_LONG),
+                col("title", ColumnType::MYSQL_TYPE_VAR_STRING),
+            ]),
+        );
+        let retyped = new_row(
+            vec![Value::Int(1), Value::from("foo")],
+            Arc::new(vec![
+                col("id", ColumnType::MYSQL_TYPE_LONGLONG),
+                col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+            ]),
+        );
+        let reordered = new_row(
+            vec![Value::from("foo"), Value::Int(1)],
+            Arc::new(vec![
+                col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+                col("id", ColumnType::MYSQL_TYPE_LONG),
+            ]),
+        );
+        assert_ne!(row.schema_fingerprint(), renamed.schema_fingerprint());
+        assert_ne!(row.schema_fingerprint(), retyped.schema_fingerprint());
+        assert_ne!(row.schema_fingerprint(), reordered.schema_fingerprint());
+    }
+
+    #[test]
+    fn should_zip_values() {
+        let short = new_row(
+            vec![Value::Int(1), Value::from("foo")],
+            Arc::new(vec![
+                col("id", ColumnType::MYSQL_TYPE_LONG),
+                col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+            ]),
+        );
+        let mut long = new_row(
+            vec![Value::Int(2), Value::from("bar"), Value::NULL],
+            Arc::new(vec![
+                col("id", ColumnType::MYSQL_TYPE_LONG),
+                col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+                col("extra", ColumnType::MYSQL_TYPE_NULL),
+            ]),
+        );
+        long.take::<String, _>("name");
+
+        let zipped = short.zip_values(&long).collect::<Vec<_>>();
+        assert_eq!(
+            zipped,
+            vec![
+                (Some(&Value::Int(1)), Some(&Value::Int(2))),
+                (Some(&Value::from("foo")), None),
+                (None, Some(&Value::NULL)),
+            ]
+        );
+        assert_eq!(long.zip_values(&short).count(), 3);
+    }
+
+    #[test]
+    fn should_get_signed_duration() {
+        let columns = Arc::new(vec![
+            col("binary", ColumnType::MYSQL_TYPE_TIME),
+            col("text", ColumnType::MYSQL_TYPE_TIME),
+            col("positive", ColumnType::MYSQL_TYPE_TIME),
+            col("int", ColumnType::MYSQL_TYPE_LONG),
+        ]);
+        let row = new_row(
+            vec![
+                Value::Time(true, 2, 3, 4, 5, 6),
+                Value::from("-051:04:05.000006"),
+                Value::from("00:00:01"),
+                Value::Int(1),
+            ],
+            columns,
+        );
+
+        let expected = Duration::new(2 * 86_400 + 3 * 3_600 + 4 * 60 + 5, 6_000);
+        assert_eq!(row.get_signed_duration(0), Some((true, expected)));
+        assert_eq!(row.get_signed_duration("text"), Some((true, expected)));
+        assert_eq!(
+            row.get_signed_duration("positive"),
+            Some((false, Duration::from_secs(1)))
+        );
+        assert_eq!(row.get_signed_duration("int"), None);
+        assert_eq!(row.get_signed_duration("missing"), None);
+    }
+
+    #[test]
+    fn should_take_remaining_values() {
+        let columns = Arc::new(vec![
+            col("a", ColumnType::MYSQL_TYPE_LONG),
+            col("b", ColumnType::MYSQL_TYPE_LONG),
+            col("c", ColumnType::MYSQL_TYPE_NULL),
+        ]);
+        let mut row = new_row(vec![Value::Int(1), Value::Int(2), Value::NULL], columns);
+        assert_eq!(row.take::<i32, _>("b"), Some(2));
+
+        assert_eq!(
+            row.take_remaining(),
+            vec![Some(Value::Int(1)), None, Some(Value::NULL)]
+        );
+        assert_eq!(row.count_present(), 0);
+        assert_eq!(row.len(), 3);
+        assert_eq!(row.take_remaining(), vec![None, None, None]);
+    }
+
+    #[test]
+    fn should_build_row() {
+        let columns = Arc::new(vec![
+            col("id", ColumnType::MYSQL_TYPE_LONG),
+            col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+        ]);
+
+        let row = RowBuilder::new(columns.clone())
+            .push(1)
+            .push("foo")
+            .build()
+            .unwrap();
+        assert_eq!(
+            row,
+            new_row(vec![Value::Int(1), Value::from("foo")], columns.clone())
+        );
+        assert!(Arc::ptr_eq(&row.columns(), &columns));
+
+        let err = RowBuilder::new(columns.clone())
+            .push(1)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SchemaError::ColumnCount {
+                expected: 2,
+                actual: 1
+            }
+        );
+        let err = RowBuilder::new(columns)
+            .push(1)
+            .push("foo")
+            .push(Value::NULL)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SchemaError::ColumnCount {
+                expected: 2,
+                actual: 3
+            }
+        );
+    }
+
+    #[test]
+    fn should_map_values() {
+        let columns = Arc::new(vec![
+            col("id", ColumnType::MYSQL_TYPE_LONG),
+            col("ssn", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("taken", ColumnType::MYSQL_TYPE_VAR_STRING),
+        ]);
+        let mut row = new_row(
+            vec![
+                Value::Int(1),
+                Value::from("123-45-6789"),
+                Value::from("foo"),
+                Value::from("bar"),
+            ],
+            columns.clone(),
+        );
+        row.take::<String, _>("taken");
+
+        let mut calls = 0;
+        let masked = row.map_values(|column, value| {
+            calls += 1;
+            match value {
+                Value::Bytes(_) if column.name_str() == "ssn" => Value::from("***"),
+                value => value,
+            }
+        });
+
+        assert_eq!(calls, 3);
+        assert!(Arc::ptr_eq(&masked.columns(), &columns));
+        assert_eq!(masked.get::<i32, _>("id"), Some(1));
+        assert_eq!(masked.get::<String, _>("ssn").unwrap(), "***");
+        assert_eq!(masked.get::<String, _>("name").unwrap(), "foo");
+        assert_eq!(masked.as_ref(3), None);
+        assert_eq!(masked.len(), 4);
+    }
+
+    #[test]
+    fn should_get_intermediate() {
+        use crate::value::convert::ConvIr;
+
+        let columns = Arc::new(vec![
+            col("num", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("text", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("taken", ColumnType::MYSQL_TYPE_LONG),
+        ]);
+        let mut row = new_row(
+            vec![Value::from("42"), Value::from("foo"), Value::Int(1)],
+            columns,
+        );
+        row.take::<i32, _>("taken");
+
+        let ir = row.get_intermediate::<i64, _>("num").unwrap().unwrap();
+        assert_eq!(ir.commit(), 42);
+
+        let ir = row.get_intermediate::<String, _>("num").unwrap().unwrap();
+        let value = ConvIr::<String>::rollback(ir);
+        assert_eq!(value, Value::from("42"));
+        assert_eq!(from_value::<u8>(value), 42);
+
+        let err = row.get_intermediate::<i64, _>("text").unwrap().unwrap_err();
+        assert_eq!(*err.value(), Value::from("foo"));
+        assert_eq!(err.type_name(), "i64");
+
+        assert!(row.get_intermediate::<i64, _>("taken").is_none());
+        assert!(row.get_intermediate::<i64, _>("missing").is_none());
+        assert_eq!(row.get::<String, _>("num").unwrap(), "42");
+    }
+
+    #[test]
+    fn should_snapshot_values() {
+        let columns = Arc::new(vec![
+            col("id", ColumnType::MYSQL_TYPE_LONG),
+            col("name", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("taken", ColumnType::MYSQL_TYPE_LONG),
+        ]);
+        let mut row = new_row(
+            vec![Value::Int(1), Value::from("foo"), Value::Int(2)],
+            columns,
+        );
+        row.take::<i32, _>("taken");
+
+        let expected = vec![Some(Value::Int(1)), Some(Value::from("foo")), None];
+        assert_eq!(row.values(), &expected[..]);
+
+        let mut values = row.to_values();
+        assert_eq!(values, expected);
+        values[0] = Some(Value::Int(100));
+        values[2] = Some(Value::NULL);
+        assert_eq!(row.values(), &expected[..]);
+
+        let mut copy = row.clone();
+        copy.place(0, Value::Int(100));
+        assert_eq!(row.values(), &expected[..]);
+        assert_eq!(copy.values()[0], Some(Value::Int(100)));
+    }
+
+    #[test]
+    fn should_try_into_tuple() {
+        let columns = Arc::new(vec![
+            col("a", ColumnType::MYSQL_TYPE_VAR_STRING),
+            col("b", ColumnType::MYSQL_TYPE_LONGLONG),
+        ]);
+        let row = new_row(vec![Value::from("foo"), Value::from("42")], columns);
+
+        let (a, b) = row.clone().try_into_tuple::<(String, i64)>().unwrap();
+        assert_eq!(a, "foo");
+        assert_eq!(b, 42);
+
+        let err = row.clone().try_into_tuple::<(String,)>().unwrap_err();
+        assert_eq!(err.0, row);
+        let err = row
+            .clone()
+            .try_into_tuple::<(String, i64, i64)>()
+            .unwrap_err();
+        assert_eq!(err.0, row);
+
+        let err = row.clone().try_into_tuple::<(i64, String)>().unwrap_err();
+        assert_eq!(err.0, row);
+        assert_eq!(err.0.get::<String, _>("a").unwrap(), "foo");
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+use std::any::Any;
+use std::fmt;
+
+use crate::constants::ColumnType;
+use crate::packets::Column;
+use crate::value::convert::FromValueError;
+use crate::value::Value;
+
+/// Runtime decoder of a column value (see `ConversionRegistry`).
+pub type Decoder = Box<dyn Fn(&Value) -> Result<Box<dyn Any>, FromValueError> + Send + Sync>;
+
+/// Registry of decoders for types that are only known at runtime (e.g. plugin-provided codecs).
+///
+/// Decoders are registered either for a column name pattern or for a column type. Name patterns
+/// are matched first, in registration order, and may contain `*` that matches any sequence of
+/// bytes. Registry is `Send + Sync`, so it could be shared between threads once populated.
+///
+/// See `Row::get_dynamic`.
+#[derive(Default)]
+pub struct ConversionRegistry {
+    by_name: Vec<(Vec<u8>, Decoder)>,
+    by_type: Vec<(ColumnType, Decoder)>,
+}
+
+impl ConversionRegistry {
+    /// Creates empty registry.
+    pub fn new() -> ConversionRegistry {
+        ConversionRegistry::default()
+    }
+
+    /// Registers decoder for columns with name matching `pattern`.
+    pub fn register_name<F>(&mut self, pattern: &str, decoder: F) -> &mut Self
+    where
+        F: Fn(&Value) -> Result<Box<dyn Any>, FromValueError> + Send + Sync + 'static,
+    {
+        self.by_name
+            .push((pattern.as_bytes().to_vec(), Box::new(decoder)));
+        self
+    }
+
+    /// Registers decoder for columns of type `column_type`.
+    pub fn register_type<F>(&mut self, column_type: ColumnType, decoder: F) -> &mut Self
+    where
+        F: Fn(&Value) -> Result<Box<dyn Any>, FromValueError> + Send + Sync + 'static,
+    {
+        self.by_type.push((column_type, Box::new(decoder)));
+        self
+    }
+
+    /// Returns decoder registered for `column`, if any.
+    pub fn decoder(&self, column: &Column) -> Option<&Decoder> {
+        self.by_name
+            .iter()
+            .find(|(pattern, _)| matches_pattern(pattern, column.name_ref()))
+            .map(|(_, decoder)| decoder)
+            .or_else(|| {
+                self.by_type
+                    .iter()
+                    .find(|(column_type, _)| *column_type == column.column_type())
+                    .map(|(_, decoder)| decoder)
+            })
+    }
+}
+
+impl fmt::Debug for ConversionRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConversionRegistry")
+            .field(
+                "names",
+                &self
+                    .by_name
+                    .iter()
+                    .map(|(pattern, _)| String::from_utf8_lossy(pattern))
+                    .collect::<Vec<_>>(),
+            )
+            .field(
+                "types",
+                &self.by_type.iter().map(|(t, _)| t).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// Matches `name` against `pattern` where `*` matches any sequence of bytes.
+fn matches_pattern(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|i| matches_pattern(rest, &name[i..])),
+        Some((b, rest)) => name.first() == Some(b) && matches_pattern(rest, &name[1..]),
+    }
+}
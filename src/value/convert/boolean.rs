@@ -0,0 +1,275 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+//! This module implements lenient conversions from/to `Value` for boolean types.
+//!
+//! The wrappers are opt-in: conversion to plain `bool` is not affected by them.
+
+use lexical::parse;
+
+use super::{ConvIr, FromValue, FromValueError, ParseIr, Value};
+
+/// Use it to parse booleans stored as SQL keywords.
+///
+/// Accepts MySql int {`0`, `1`} or bytes {`"0"`, `"1"`, `"TRUE"`, `"FALSE"`, `"T"`, `"F"`}.
+/// Keywords are matched case-insensitively and may be surrounded by a single pair of `'` or `"`
+/// quotes.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct SqlBool(pub bool);
+
+/// Use it to parse C-style booleans, where any nonzero number is `true`.
+///
+/// Matches SQL `WHERE col` truthiness: `Value::Int`, `Value::UInt` and `Value::Float` are `true`
+/// if nonzero, bytes are parsed as an integer or, failing that, as a float and treated likewise.
+/// Empty, non-numeric or non-finite bytes are rejected.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct CBool(pub bool);
+
+/// Use it to parse booleans stored as `'Y'`/`'N'` (e.g. `CHAR(1)` columns of legacy schemas).
+///
+/// Accepts bytes {`"Y"`, `"N"`} (case-insensitively) in addition to everything `SqlBool` accepts.
+/// `From<YnBool> for Value` emits `"Y"` or `"N"`.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct YnBool(pub bool);
+
+/// Strips a single pair of surrounding `'` or `"` quotes.
+fn unquote(bytes: &[u8]) -> &[u8] {
+    match bytes {
+        [b'\'', inner @ .., b'\''] | [b'"', inner @ .., b'"'] => inner,
+        _ => bytes,
+    }
+}
+
+fn parse_sql_bool(bytes: &[u8]) -> Option<bool> {
+    const TRUE: &[&[u8]] = &[b"1", b"true", b"t"];
+    const FALSE: &[&[u8]] = &[b"0", b"false", b"f"];
+
+    let bytes = unquote(bytes);
+    if TRUE.iter().any(|x| x.eq_ignore_ascii_case(bytes)) {
+        Some(true)
+    } else if FALSE.iter().any(|x| x.eq_ignore_ascii_case(bytes)) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+impl ConvIr<SqlBool> for ParseIr<SqlBool> {
+    fn new(v: Value) -> Result<ParseIr<SqlBool>, FromValueError> {
+        let output = match v {
+            Value::Int(0) | Value::UInt(0) => false,
+            Value::Int(1) | Value::UInt(1) => true,
+            Value::Bytes(ref bytes) => match parse_sql_bool(bytes) {
+                Some(output) => output,
+                None => return Err(FromValueError(v)),
+            },
+            v => return Err(FromValueError(v)),
+        };
+        Ok(ParseIr {
+            value: v,
+            output: SqlBool(output),
+        })
+    }
+    fn commit(self) -> SqlBool {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl ConvIr<CBool> for ParseIr<CBool> {
+    fn new(v: Value) -> Result<ParseIr<CBool>, FromValueError> {
+        let output = match v {
+            Value::Int(x) => x != 0,
+            Value::UInt(x) => x != 0,
+            Value::Float(x) => x != 0.0,
+            Value::Bytes(ref bytes) => match parse::<i128, _>(bytes) {
+                Ok(x) => x != 0,
+                Err(_) => match parse::<f64, _>(bytes) {
+                    Ok(x) if x.is_finite() => x != 0.0,
+                    _ => return Err(FromValueError(v)),
+                },
+            },
+            v => return Err(FromValueError(v)),
+        };
+        Ok(ParseIr {
+            value: v,
+            output: CBool(output),
+        })
+    }
+    fn commit(self) -> CBool {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl ConvIr<YnBool> for ParseIr<YnBool> {
+    fn new(v: Value) -> Result<ParseIr<YnBool>, FromValueError> {
+        let output = match v {
+            Value::Bytes(ref bytes) if bytes.eq_ignore_ascii_case(b"y") => true,
+            Value::Bytes(ref bytes) if bytes.eq_ignore_ascii_case(b"n") => false,
+            v => {
+                let ParseIr { value, output } = ParseIr::<SqlBool>::new(v)?;
+                return Ok(ParseIr {
+                    value,
+                    output: YnBool(output.0),
+                });
+            }
+        };
+        Ok(ParseIr {
+            value: v,
+            output: YnBool(output),
+        })
+    }
+    fn commit(self) -> YnBool {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl FromValue for SqlBool {
+    type Intermediate = ParseIr<SqlBool>;
+}
+
+impl FromValue for CBool {
+    type Intermediate = ParseIr<CBool>;
+}
+
+impl FromValue for YnBool {
+    type Intermediate = ParseIr<YnBool>;
+}
+
+impl From<SqlBool> for Value {
+    fn from(x: SqlBool) -> Value {
+        x.0.into()
+    }
+}
+
+impl From<YnBool> for Value {
+    fn from(x: YnBool) -> Value {
+        Value::Bytes(if x.0 { b"Y".to_vec() } else { b"N".to_vec() })
+    }
+}
+
+impl From<CBool> for Value {
+    fn from(x: CBool) -> Value {
+        x.0.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn should_parse_sql_bool_keywords() {
+        for (input, expected) in &[
+            (&b"TRUE"[..], true),
+            (b"true", true),
+            (b"True", true),
+            (b"t", true),
+            (b"'TRUE'", true),
+            (b"\"t\"", true),
+            (b"1", true),
+            (b"FALSE", false),
+            (b"f", false),
+            (b"F", false),
+            (b"'false'", false),
+            (b"0", false),
+        ] {
+            let value = Value::Bytes(input.to_vec());
+            assert_eq!(from_value::<SqlBool>(value), SqlBool(*expected));
+        }
+        assert_eq!(from_value::<SqlBool>(Value::Int(1)), SqlBool(true));
+        assert_eq!(from_value::<SqlBool>(Value::Int(0)), SqlBool(false));
+    }
+
+    #[test]
+    fn should_reject_unknown_sql_bool() {
+        for input in &[&b"2"[..], b"yes", b"'TRUE", b"", b"''"] {
+            let value = Value::Bytes(input.to_vec());
+            assert_eq!(
+                from_value_opt::<SqlBool>(value.clone()).unwrap_err().0,
+                value
+            );
+        }
+        assert!(from_value_opt::<SqlBool>(Value::Int(2)).is_err());
+    }
+
+    #[test]
+    fn should_parse_yn_bool() {
+        for (input, expected) in &[
+            (&b"Y"[..], true),
+            (b"y", true),
+            (b"N", false),
+            (b"n", false),
+            (b"TRUE", true),
+            (b"0", false),
+        ] {
+            let value = Value::Bytes(input.to_vec());
+            assert_eq!(from_value::<YnBool>(value), YnBool(*expected));
+        }
+        assert_eq!(from_value::<YnBool>(Value::Int(1)), YnBool(true));
+        assert_eq!(Value::from(YnBool(true)), Value::from("Y"));
+        assert_eq!(Value::from(YnBool(false)), Value::from("N"));
+    }
+
+    #[test]
+    fn should_reject_unknown_yn_bool() {
+        for input in &[&b"X"[..], b"yes", b"YN", b""] {
+            let value = Value::Bytes(input.to_vec());
+            assert_eq!(
+                from_value_opt::<YnBool>(value.clone()).unwrap_err().0,
+                value
+            );
+        }
+        assert!(from_value_opt::<bool>(Value::from("Y")).is_err());
+        assert!(from_value_opt::<SqlBool>(Value::from("Y")).is_err());
+    }
+
+    #[test]
+    fn should_treat_nonzero_as_true() {
+        for (input, expected) in &[
+            (Value::Int(0), false),
+            (Value::Int(2), true),
+            (Value::Int(-1), true),
+            (Value::Int(-5), true),
+            (Value::UInt(u64::MAX), true),
+            (Value::Float(0.0), false),
+            (Value::Float(0.5), true),
+            (Value::from("0"), false),
+            (Value::from("2"), true),
+            (Value::from("-1"), true),
+            (Value::from("1.5"), true),
+            (Value::from("0.0"), false),
+            (Value::from("-0.25"), true),
+        ] {
+            assert_eq!(from_value::<CBool>(input.clone()), CBool(*expected));
+        }
+    }
+
+    #[test]
+    fn should_reject_non_numeric_c_bool() {
+        for input in &["", "true", "1.5x", " 1", "nan", "inf"] {
+            let value = Value::from(*input);
+            assert_eq!(from_value_opt::<CBool>(value.clone()).unwrap_err().0, value);
+        }
+        assert!(from_value_opt::<CBool>(Value::NULL).is_err());
+    }
+
+    #[test]
+    fn strict_bool_should_reject_keywords() {
+        assert!(from_value_opt::<bool>(Value::Bytes(b"TRUE".to_vec())).is_err());
+        assert!(from_value_opt::<bool>(Value::Int(2)).is_err());
+    }
+}
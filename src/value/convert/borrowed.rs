@@ -0,0 +1,140 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+//! This module implements borrowing conversion from `&Value` (see `FromValueRef`).
+
+use lexical::parse;
+
+use std::str::from_utf8;
+
+use super::{from_value_opt, FromValueError, Value};
+
+/// Conversion from a borrowed `Value`.
+///
+/// Unlike `FromValue` it doesn't need to take the value by value, so string and byte slices
+/// could be borrowed from a row without cloning. The value is only cloned into the error.
+pub trait FromValueRef<'a>: Sized {
+    /// Will return `Err(FromValueError(v.clone()))` if could not convert `v` to `Self`.
+    fn from_value_ref(v: &'a Value) -> Result<Self, FromValueError>;
+}
+
+macro_rules! impl_from_value_ref_num {
+    ($($t:ident),*) => {
+        $(
+            impl<'a> FromValueRef<'a> for $t {
+                fn from_value_ref(v: &'a Value) -> Result<$t, FromValueError> {
+                    match *v {
+                        Value::Bytes(ref bytes) => {
+                            parse(bytes).map_err(|_| FromValueError(v.clone()))
+                        }
+                        _ => from_value_opt(v.clone()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_value_ref_num!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize);
+
+/// Bytes are rejected if parsed to a non-finite value (as `FromValue` does).
+macro_rules! impl_from_value_ref_float {
+    ($($t:ident),*) => {
+        $(
+            impl<'a> FromValueRef<'a> for $t {
+                fn from_value_ref(v: &'a Value) -> Result<$t, FromValueError> {
+                    match *v {
+                        Value::Bytes(ref bytes) => match parse::<$t, _>(bytes) {
+                            Ok(x) if x.is_finite() => Ok(x),
+                            _ => Err(FromValueError(v.clone())),
+                        },
+                        _ => from_value_opt(v.clone()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_value_ref_float!(f32, f64);
+
+impl<'a> FromValueRef<'a> for &'a [u8] {
+    fn from_value_ref(v: &'a Value) -> Result<&'a [u8], FromValueError> {
+        match *v {
+            Value::Bytes(ref bytes) => Ok(bytes),
+            _ => Err(FromValueError(v.clone())),
+        }
+    }
+}
+
+impl<'a> FromValueRef<'a> for &'a str {
+    fn from_value_ref(v: &'a Value) -> Result<&'a str, FromValueError> {
+        match *v {
+            Value::Bytes(ref bytes) => from_utf8(bytes).map_err(|_| FromValueError(v.clone())),
+            _ => Err(FromValueError(v.clone())),
+        }
+    }
+}
+
+impl<'a> FromValueRef<'a> for &'a Value {
+    fn from_value_ref(v: &'a Value) -> Result<&'a Value, FromValueError> {
+        Ok(v)
+    }
+}
+
+impl<'a, T: FromValueRef<'a>> FromValueRef<'a> for Option<T> {
+    fn from_value_ref(v: &'a Value) -> Result<Option<T>, FromValueError> {
+        match *v {
+            Value::NULL => Ok(None),
+            _ => T::from_value_ref(v).map(Some),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FromValueRef;
+    use crate::value::Value;
+
+    #[test]
+    fn should_borrow_from_value() {
+        let value = Value::from("foo");
+        let s = <&str>::from_value_ref(&value).unwrap();
+        assert_eq!(s, "foo");
+        assert_eq!(<&[u8]>::from_value_ref(&value).unwrap(), b"foo");
+        assert_eq!(<&Value>::from_value_ref(&value).unwrap(), &value);
+
+        assert_eq!(i64::from_value_ref(&Value::from("-42")).unwrap(), -42);
+        assert_eq!(u8::from_value_ref(&Value::Int(42)).unwrap(), 42);
+        assert_eq!(f64::from_value_ref(&Value::Float(1.5)).unwrap(), 1.5);
+        assert_eq!(Option::<&str>::from_value_ref(&Value::NULL).unwrap(), None);
+        assert_eq!(Option::<&str>::from_value_ref(&value).unwrap(), Some("foo"));
+    }
+
+    #[test]
+    fn should_not_borrow_inconvertible_value() {
+        let value = Value::Bytes(vec![0xff]);
+        assert_eq!(<&str>::from_value_ref(&value).unwrap_err().0, value);
+        assert!(<&str>::from_value_ref(&Value::Int(1)).is_err());
+        assert!(u8::from_value_ref(&Value::from("256")).is_err());
+        assert!(i64::from_value_ref(&Value::NULL).is_err());
+    }
+
+    #[test]
+    fn should_reject_non_finite_float_bytes() {
+        for input in &["nan", "NaN", "inf", "-inf", "Infinity"] {
+            let value = Value::from(*input);
+            assert_eq!(f64::from_value_ref(&value).unwrap_err().0, value);
+            assert_eq!(f32::from_value_ref(&value).unwrap_err().0, value);
+        }
+        // out of f32 range
+        assert!(f32::from_value_ref(&Value::from("1e39")).is_err());
+        assert_eq!(f64::from_value_ref(&Value::from("1.5")).unwrap(), 1.5);
+        assert_eq!(f32::from_value_ref(&Value::from("-0.5")).unwrap(), -0.5);
+    }
+}
@@ -0,0 +1,711 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+//! This module implements lenient conversions from `Value` for date and time types.
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+
+use std::borrow::Cow;
+use std::cmp::min;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::str::from_utf8;
+
+use super::{
+    from_value_opt, parse_mysql_datetime_string, ConvIr, FromValue, FromValueError, OptionIr,
+    ParseIr, Value,
+};
+
+/// Use it to parse time strings that use either `.` or `,` as the fractional seconds separator.
+///
+/// Locale-formatted times may look like `"12:34:56,789"`. This wrapper normalizes the separator
+/// and then converts the value to `T` (i.e. `NaiveTime`, `Duration`, `NaiveDateTime`, ...).
+/// Fractional part longer than six digits is truncated to microseconds. Value with more than one
+/// separator is rejected.
+///
+/// ```
+/// use chrono::NaiveTime;
+/// use mysql_common::value::{convert::{from_value, LocaleTime}, Value};
+///
+/// let LocaleTime(time) = from_value::<LocaleTime<NaiveTime>>(Value::from("12:34:56,789"));
+/// assert_eq!(time, NaiveTime::from_hms_milli_opt(12, 34, 56, 789).unwrap());
+/// ```
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct LocaleTime<T>(pub T);
+
+/// Intermediate result of a Value-to-LocaleTime conversion.
+#[derive(Debug)]
+pub struct LocaleTimeIr<Ir> {
+    bytes: Option<Vec<u8>>,
+    ir: Ir,
+}
+
+/// Replaces `,` fractional separator with `.` and truncates fractional part to microseconds.
+///
+/// Returns `Cow::Borrowed` if there is no separator. Returns `None` if anything but ASCII digits
+/// follows the separator (this includes a second separator).
+fn normalize_fraction(bytes: &[u8]) -> Option<Cow<'_, [u8]>> {
+    let pos = match bytes.iter().position(|b| *b == b'.' || *b == b',') {
+        Some(pos) => pos,
+        None => return Some(Cow::Borrowed(bytes)),
+    };
+
+    let fraction = &bytes[pos + 1..];
+    if !fraction.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+
+    let mut output = bytes[..pos + 1 + min(fraction.len(), 6)].to_vec();
+    output[pos] = b'.';
+    Some(Cow::Owned(output))
+}
+
+impl<T, Ir> ConvIr<LocaleTime<T>> for LocaleTimeIr<Ir>
+where
+    T: FromValue<Intermediate = Ir>,
+    Ir: ConvIr<T>,
+{
+    fn new(v: Value) -> Result<LocaleTimeIr<Ir>, FromValueError> {
+        match v {
+            Value::Bytes(bytes) => {
+                let ir = match normalize_fraction(&bytes) {
+                    Some(Cow::Borrowed(_)) => {
+                        return T::get_intermediate(Value::Bytes(bytes))
+                            .map(|ir| LocaleTimeIr { bytes: None, ir });
+                    }
+                    Some(Cow::Owned(normalized)) => {
+                        T::get_intermediate(Value::Bytes(normalized)).ok()
+                    }
+                    None => None,
+                };
+                match ir {
+                    Some(ir) => Ok(LocaleTimeIr {
+                        bytes: Some(bytes),
+                        ir,
+                    }),
+                    None => Err(FromValueError(Value::Bytes(bytes))),
+                }
+            }
+            v => T::get_intermediate(v).map(|ir| LocaleTimeIr { bytes: None, ir }),
+        }
+    }
+    fn commit(self) -> LocaleTime<T> {
+        LocaleTime(self.ir.commit())
+    }
+    fn rollback(self) -> Value {
+        match self.bytes {
+            Some(bytes) => Value::Bytes(bytes),
+            None => self.ir.rollback(),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for LocaleTime<T> {
+    type Intermediate = LocaleTimeIr<T::Intermediate>;
+}
+
+impl<T: Into<Value>> From<LocaleTime<T>> for Value {
+    fn from(x: LocaleTime<T>) -> Value {
+        x.0.into()
+    }
+}
+
+/// Use it to parse dates with named months, such as `"02 Jan 2023"` or `"January 2, 2023"`.
+///
+/// Supported formats are `day month year` and `month day year` where parts are separated
+/// by spaces, `-` or `, `. Month is either a full English month name or its three-letter
+/// abbreviation, matched case-insensitively.
+///
+/// `From<NamedMonthDate> for Value` emits `Value::Date`.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct NamedMonthDate(pub NaiveDate);
+
+fn parse_month_name(name: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+
+    let name = name.to_ascii_lowercase();
+    MONTHS
+        .iter()
+        .position(|month| name == *month || (name.len() == 3 && month.starts_with(&*name)))
+        .map(|i| i as u32 + 1)
+}
+
+fn parse_named_month_date(bytes: &[u8]) -> Option<NaiveDate> {
+    let parts = from_utf8(bytes)
+        .ok()?
+        .split(&[' ', '-', ','][..])
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<_>>();
+    let (first, second, year) = match parts[..] {
+        [first, second, year] => (first, second, year),
+        _ => return None,
+    };
+
+    let number = |x: &str| {
+        if x.bytes().all(|b| b.is_ascii_digit()) && x.len() <= 4 {
+            x.parse::<u32>().ok()
+        } else {
+            None
+        }
+    };
+    let (day, month) = match parse_month_name(first) {
+        Some(month) => (number(second)?, month),
+        None => (number(first)?, parse_month_name(second)?),
+    };
+    if year.len() != 4 {
+        return None;
+    }
+    NaiveDate::from_ymd_opt(number(year)? as i32, month, day)
+}
+
+impl ConvIr<NamedMonthDate> for ParseIr<NamedMonthDate> {
+    fn new(v: Value) -> Result<ParseIr<NamedMonthDate>, FromValueError> {
+        let output = match v {
+            Value::Bytes(ref bytes) => parse_named_month_date(bytes),
+            _ => None,
+        };
+        match output {
+            Some(output) => Ok(ParseIr {
+                value: v,
+                output: NamedMonthDate(output),
+            }),
+            None => Err(FromValueError(v)),
+        }
+    }
+    fn commit(self) -> NamedMonthDate {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl FromValue for NamedMonthDate {
+    type Intermediate = ParseIr<NamedMonthDate>;
+}
+
+impl From<NamedMonthDate> for Value {
+    fn from(x: NamedMonthDate) -> Value {
+        x.0.into()
+    }
+}
+
+/// Datetime could not be represented as `Value` without loss of precision
+/// (see `Value::try_from_naive_datetime_exact`).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PrecisionError(pub NaiveDateTime);
+
+impl fmt::Display for PrecisionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Datetime `{}` has sub-microsecond precision not supported by MySql",
+            self.0
+        )
+    }
+}
+
+impl Error for PrecisionError {
+    fn description(&self) -> &str {
+        "Datetime has sub-microsecond precision not supported by MySql"
+    }
+}
+
+impl Value {
+    /// Converts `x` to `Value` like `From<NaiveDateTime>` does, but fails instead of truncating
+    /// sub-microsecond part of `x`.
+    ///
+    /// Will panic if the year of `x` is not in range [1000, 9999] (as `From` does).
+    pub fn try_from_naive_datetime_exact(x: NaiveDateTime) -> Result<Value, PrecisionError> {
+        match x.nanosecond() % 1000 {
+            0 => Ok(Value::from(x)),
+            _ => Err(PrecisionError(x)),
+        }
+    }
+}
+
+impl Value {
+    /// Returns `true` if this is a MySql zero date (`0000-00-00` or `0000-00-00 00:00:00`).
+    ///
+    /// Zero dates are allowed by some `sql_mode`s and couldn't be converted to chrono types.
+    /// Conversion to `NaiveDate` or `NaiveDateTime` fails with a reason that mentions zero date
+    /// (see `ConversionError::reason`).
+    pub fn is_zero_date(&self) -> bool {
+        match *self {
+            Value::Date(0, 0, 0, ..) => true,
+            Value::Bytes(ref bytes) => {
+                matches!(parse_mysql_datetime_string(bytes), Some((0, 0, 0, ..)))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Nullable date or datetime where MySql zero date is considered `None` (as `NULL` is).
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use mysql_common::value::{convert::{from_value, ZeroDateAsNone}, Value};
+///
+/// let ZeroDateAsNone(date) = from_value::<ZeroDateAsNone<NaiveDate>>(Value::from("0000-00-00"));
+/// assert_eq!(date, None);
+/// ```
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct ZeroDateAsNone<T>(pub Option<T>);
+
+impl<T, Ir> ConvIr<ZeroDateAsNone<T>> for OptionIr<Ir>
+where
+    T: FromValue<Intermediate = Ir>,
+    Ir: ConvIr<T>,
+{
+    fn new(v: Value) -> Result<OptionIr<Ir>, FromValueError> {
+        if v == Value::NULL || v.is_zero_date() {
+            Ok(OptionIr {
+                value: Some(v),
+                ir: None,
+            })
+        } else {
+            T::get_intermediate(v).map(|ir| OptionIr {
+                value: None,
+                ir: Some(ir),
+            })
+        }
+    }
+    fn commit(self) -> ZeroDateAsNone<T> {
+        ZeroDateAsNone(self.ir.map(ConvIr::commit))
+    }
+    fn rollback(self) -> Value {
+        match self.value {
+            Some(v) => v,
+            None => self.ir.map(ConvIr::rollback).unwrap_or(Value::NULL),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for ZeroDateAsNone<T> {
+    type Intermediate = OptionIr<T::Intermediate>;
+}
+
+impl<T: Into<Value>> From<ZeroDateAsNone<T>> for Value {
+    fn from(x: ZeroDateAsNone<T>) -> Value {
+        x.0.into()
+    }
+}
+
+/// Use it to read a `DateTime<FixedOffset>` from a value holding wall-clock time at a known
+/// offset of `EAST_SECS` seconds east of UTC.
+///
+/// Plain `DateTime<FixedOffset>` conversion assumes UTC. Converts back to the wall-clock time at
+/// the offset (unlike `DateTime<FixedOffset>`, that is converted to UTC wall-clock time).
+/// Offset out of range (more than a day) makes every conversion fail.
+///
+/// ```
+/// use mysql_common::value::{convert::{from_value, AssumedOffset}, Value};
+///
+/// // UTC+03:00
+/// let value = Value::Date(2019, 11, 27, 12, 30, 0, 0);
+/// let AssumedOffset(datetime) = from_value::<AssumedOffset<10800>>(value.clone());
+/// assert_eq!(datetime.to_rfc3339(), "2019-11-27T12:30:00+03:00");
+/// assert_eq!(Value::from(AssumedOffset::<10800>(datetime)), value);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct AssumedOffset<const EAST_SECS: i32>(pub DateTime<FixedOffset>);
+
+impl<const EAST_SECS: i32> ConvIr<AssumedOffset<EAST_SECS>> for ParseIr<AssumedOffset<EAST_SECS>> {
+    fn new(v: Value) -> Result<ParseIr<AssumedOffset<EAST_SECS>>, FromValueError> {
+        let offset = match FixedOffset::east_opt(EAST_SECS) {
+            Some(offset) => offset,
+            None => return Err(FromValueError(v)),
+        };
+        let ParseIr { value, output } = ParseIr::<NaiveDateTime>::new(v)?;
+        match offset.from_local_datetime(&output).single() {
+            Some(output) => Ok(ParseIr {
+                value,
+                output: AssumedOffset(output),
+            }),
+            None => Err(FromValueError(value)),
+        }
+    }
+    fn commit(self) -> AssumedOffset<EAST_SECS> {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl<const EAST_SECS: i32> FromValue for AssumedOffset<EAST_SECS> {
+    type Intermediate = ParseIr<AssumedOffset<EAST_SECS>>;
+}
+
+impl<const EAST_SECS: i32> From<AssumedOffset<EAST_SECS>> for Value {
+    fn from(x: AssumedOffset<EAST_SECS>) -> Value {
+        x.0.naive_local().into()
+    }
+}
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+/// Use it to convert integers holding nanoseconds since Unix epoch (e.g. high-resolution
+/// timestamps stored as `BIGINT`).
+///
+/// Supported `T` are `DateTime<Utc>`, that preserves nanoseconds, and `NaiveDateTime` (UTC),
+/// that is truncated to microseconds (as `Value::Date`). Negative values are before the epoch.
+/// Values out of `chrono` range are rejected. Converts back to nanoseconds since epoch.
+///
+/// ```
+/// use chrono::{DateTime, Utc};
+/// use mysql_common::value::{convert::{from_value, EpochNanos}, Value};
+///
+/// let value = Value::Int(1_600_000_000_123_456_789);
+/// let EpochNanos(ts) = from_value::<EpochNanos<DateTime<Utc>>>(value);
+/// assert_eq!(ts.to_rfc3339(), "2020-09-13T12:26:40.123456789+00:00");
+/// ```
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct EpochNanos<T>(pub T);
+
+fn datetime_from_nanos(v: &Value) -> Option<DateTime<Utc>> {
+    let nanos = from_value_opt::<i128>(v.clone()).ok()?;
+    let secs = i64::try_from(nanos.div_euclid(NANOS_PER_SEC)).ok()?;
+    DateTime::from_timestamp(secs, nanos.rem_euclid(NANOS_PER_SEC) as u32)
+}
+
+fn datetime_to_nanos(x: DateTime<Utc>) -> i128 {
+    i128::from(x.timestamp()) * NANOS_PER_SEC + i128::from(x.timestamp_subsec_nanos())
+}
+
+impl ConvIr<EpochNanos<DateTime<Utc>>> for ParseIr<EpochNanos<DateTime<Utc>>> {
+    fn new(v: Value) -> Result<ParseIr<EpochNanos<DateTime<Utc>>>, FromValueError> {
+        match datetime_from_nanos(&v) {
+            Some(output) => Ok(ParseIr {
+                value: v,
+                output: EpochNanos(output),
+            }),
+            None => Err(FromValueError(v)),
+        }
+    }
+    fn commit(self) -> EpochNanos<DateTime<Utc>> {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl ConvIr<EpochNanos<NaiveDateTime>> for ParseIr<EpochNanos<NaiveDateTime>> {
+    fn new(v: Value) -> Result<ParseIr<EpochNanos<NaiveDateTime>>, FromValueError> {
+        let output = datetime_from_nanos(&v).and_then(|x| {
+            let naive = x.naive_utc();
+            naive.with_nanosecond(naive.nanosecond() / 1_000 * 1_000)
+        });
+        match output {
+            Some(output) => Ok(ParseIr {
+                value: v,
+                output: EpochNanos(output),
+            }),
+            None => Err(FromValueError(v)),
+        }
+    }
+    fn commit(self) -> EpochNanos<NaiveDateTime> {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl FromValue for EpochNanos<DateTime<Utc>> {
+    type Intermediate = ParseIr<EpochNanos<DateTime<Utc>>>;
+}
+
+impl FromValue for EpochNanos<NaiveDateTime> {
+    type Intermediate = ParseIr<EpochNanos<NaiveDateTime>>;
+}
+
+impl From<EpochNanos<DateTime<Utc>>> for Value {
+    fn from(x: EpochNanos<DateTime<Utc>>) -> Value {
+        datetime_to_nanos(x.0).into()
+    }
+}
+
+impl From<EpochNanos<NaiveDateTime>> for Value {
+    fn from(x: EpochNanos<NaiveDateTime>) -> Value {
+        datetime_to_nanos(x.0.and_utc()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc};
+
+    use std::time::Duration;
+
+    use super::super::*;
+    use super::PrecisionError;
+
+    #[test]
+    fn should_accept_comma_as_fractional_separator() {
+        let comma = Value::Bytes(b"12:34:56,789".to_vec());
+        let dot = Value::Bytes(b"12:34:56.789".to_vec());
+
+        let LocaleTime(time) = from_value::<LocaleTime<NaiveTime>>(comma.clone());
+        let LocaleTime(time2) = from_value::<LocaleTime<NaiveTime>>(dot.clone());
+        assert_eq!(
+            time,
+            NaiveTime::from_hms_micro_opt(12, 34, 56, 789_000).unwrap()
+        );
+        assert_eq!(time, time2);
+
+        let LocaleTime(duration) = from_value::<LocaleTime<Duration>>(comma);
+        let LocaleTime(duration2) = from_value::<LocaleTime<Duration>>(dot);
+        assert_eq!(duration, Duration::new(45_296, 789_000_000));
+        assert_eq!(duration, duration2);
+    }
+
+    #[test]
+    fn should_truncate_fraction_to_micros() {
+        let value = Value::Bytes(b"12:34:56,1234567".to_vec());
+        let LocaleTime(time) = from_value::<LocaleTime<NaiveTime>>(value);
+        assert_eq!(
+            time,
+            NaiveTime::from_hms_micro_opt(12, 34, 56, 123_456).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_reject_double_separator() {
+        let value = Value::Bytes(b"12:34:56,,789".to_vec());
+        let err = from_value_opt::<LocaleTime<NaiveTime>>(value.clone()).unwrap_err();
+        assert_eq!(err.0, value);
+
+        let value = Value::Bytes(b"12:34:56.7,89".to_vec());
+        assert!(from_value_opt::<LocaleTime<NaiveTime>>(value).is_err());
+    }
+
+    #[test]
+    fn should_reject_trailing_garbage_after_fraction() {
+        for input in &["12:34:56.123456xyz", "12:34:56,1234567x", "12:34:56,7 "] {
+            let value = Value::Bytes(input.as_bytes().to_vec());
+            let err = from_value_opt::<LocaleTime<NaiveTime>>(value.clone()).unwrap_err();
+            assert_eq!(err.0, value);
+        }
+
+        let value = Value::Bytes(b"12:34:56".to_vec());
+        let ir = LocaleTime::<NaiveTime>::get_intermediate(value.clone()).unwrap();
+        assert_eq!(ir.rollback(), value);
+    }
+
+    #[test]
+    fn strict_conversion_should_reject_comma() {
+        let value = Value::Bytes(b"12:34:56,789".to_vec());
+        assert!(from_value_opt::<NaiveTime>(value).is_err());
+    }
+
+    #[test]
+    fn should_rollback_to_original_bytes() {
+        let value = Value::Bytes(b"12:34:56,789".to_vec());
+        let ir = LocaleTime::<NaiveTime>::get_intermediate(value.clone()).unwrap();
+        assert_eq!(ir.rollback(), value);
+    }
+
+    #[test]
+    fn should_parse_named_month_date() {
+        let expected = NaiveDate::from_ymd_opt(2023, 1, 2).unwrap();
+        for input in &[
+            "02 Jan 2023",
+            "2 January 2023",
+            "2 JANUARY 2023",
+            "02-jan-2023",
+            "Jan 2 2023",
+            "January 2, 2023",
+        ] {
+            let NamedMonthDate(date) = from_value(Value::from(*input));
+            assert_eq!(date, expected, "{}", input);
+        }
+        assert_eq!(
+            Value::from(NamedMonthDate(expected)),
+            Value::Date(2023, 1, 2, 0, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_named_month_date() {
+        for input in &[
+            "02 Jam 2023",
+            "02 Ja 2023",
+            "31 Feb 2023",
+            "02 01 2023",
+            "2023-01-02",
+            "02 Jan 23",
+            "02 Jan 2023 10:00",
+            "",
+        ] {
+            let value = Value::from(*input);
+            let err = from_value_opt::<NamedMonthDate>(value.clone()).unwrap_err();
+            assert_eq!(err.0, value, "{}", input);
+        }
+        assert!(from_value_opt::<NaiveDate>(Value::from("02 Jan 2023")).is_err());
+    }
+
+    #[test]
+    fn should_convert_datetime_exactly() {
+        let date = NaiveDate::from_ymd_opt(2019, 11, 27).unwrap();
+
+        let aligned = date.and_hms_nano_opt(12, 30, 0, 123_456_000).unwrap();
+        assert_eq!(
+            Value::try_from_naive_datetime_exact(aligned).unwrap(),
+            Value::Date(2019, 11, 27, 12, 30, 0, 123_456)
+        );
+
+        let precise = date.and_hms_nano_opt(12, 30, 0, 500).unwrap();
+        assert_eq!(
+            Value::try_from_naive_datetime_exact(precise).unwrap_err(),
+            PrecisionError(precise)
+        );
+        assert_eq!(
+            Value::from(precise),
+            Value::Date(2019, 11, 27, 12, 30, 0, 0)
+        );
+    }
+
+    #[test]
+    fn should_detect_zero_dates() {
+        let zero_dates = [
+            Value::from("0000-00-00"),
+            Value::from("0000-00-00 00:00:00"),
+            Value::from("0000-00-00 00:00:00.000000"),
+            Value::Date(0, 0, 0, 0, 0, 0, 0),
+        ];
+        for value in &zero_dates {
+            assert!(value.is_zero_date(), "{:?}", value);
+
+            let err = NaiveDate::from_value_explained(value.clone()).unwrap_err();
+            assert_eq!(err.value(), value);
+            assert!(err.reason().unwrap().contains("zero date"));
+            let err = NaiveDateTime::from_value_explained(value.clone()).unwrap_err();
+            assert!(err.reason().unwrap().contains("zero date"));
+
+            assert_eq!(
+                from_value::<ZeroDateAsNone<NaiveDate>>(value.clone()),
+                ZeroDateAsNone(None)
+            );
+            assert_eq!(
+                from_value::<ZeroDateAsNone<NaiveDateTime>>(value.clone()),
+                ZeroDateAsNone(None)
+            );
+        }
+
+        // garbage is not a zero date
+        let err = NaiveDate::from_value_explained(Value::from("2019-13-45")).unwrap_err();
+        assert_eq!(err.reason(), None);
+        assert!(!Value::from("foo").is_zero_date());
+        assert!(!Value::from("2019-00-00").is_zero_date());
+        assert!(from_value_opt::<ZeroDateAsNone<NaiveDate>>(Value::from("foo")).is_err());
+
+        assert_eq!(
+            from_value::<ZeroDateAsNone<NaiveDate>>(Value::from("2019-11-27")),
+            ZeroDateAsNone(NaiveDate::from_ymd_opt(2019, 11, 27))
+        );
+        assert_eq!(
+            from_value::<ZeroDateAsNone<NaiveDate>>(Value::NULL),
+            ZeroDateAsNone(None)
+        );
+    }
+
+    #[test]
+    fn should_convert_epoch_nanos() {
+        let nanos = 1_600_000_000_123_456_789_i64;
+
+        let EpochNanos(datetime) = from_value::<EpochNanos<DateTime<Utc>>>(Value::Int(nanos));
+        assert_eq!(datetime.timestamp(), 1_600_000_000);
+        assert_eq!(datetime.timestamp_subsec_nanos(), 123_456_789);
+        assert_eq!(Value::from(EpochNanos(datetime)), Value::Int(nanos));
+
+        let EpochNanos(naive) = from_value::<EpochNanos<NaiveDateTime>>(Value::UInt(nanos as u64));
+        assert_eq!(
+            naive,
+            datetime.naive_utc().with_nanosecond(123_456_000).unwrap()
+        );
+        assert_eq!(
+            Value::from(EpochNanos(naive)),
+            Value::Int(1_600_000_000_123_456_000)
+        );
+
+        let text = Value::from(nanos.to_string());
+        assert_eq!(
+            from_value::<EpochNanos<DateTime<Utc>>>(text),
+            EpochNanos(datetime)
+        );
+    }
+
+    #[test]
+    fn should_convert_negative_epoch_nanos() {
+        let EpochNanos(datetime) = from_value::<EpochNanos<DateTime<Utc>>>(Value::Int(-1));
+        assert_eq!(datetime.timestamp(), -1);
+        assert_eq!(datetime.timestamp_subsec_nanos(), 999_999_999);
+        assert_eq!(Value::from(EpochNanos(datetime)), Value::Int(-1));
+    }
+
+    #[test]
+    fn should_reject_out_of_range_epoch_nanos() {
+        for value in &[
+            Value::from(i128::MAX.to_string()),
+            Value::from(i128::MIN.to_string()),
+            Value::from("foo"),
+            Value::Float(1.0),
+        ] {
+            assert!(from_value_opt::<EpochNanos<DateTime<Utc>>>(value.clone()).is_err());
+            assert_eq!(
+                from_value_opt::<EpochNanos<NaiveDateTime>>(value.clone())
+                    .unwrap_err()
+                    .0,
+                *value
+            );
+        }
+    }
+
+    #[test]
+    fn should_assume_offset() {
+        let value = Value::Date(2019, 11, 27, 12, 30, 0, 0);
+        let AssumedOffset(datetime) = from_value::<AssumedOffset<10800>>(value.clone());
+        assert_eq!(datetime.offset().local_minus_utc(), 10800);
+        assert_eq!(
+            datetime,
+            Utc.with_ymd_and_hms(2019, 11, 27, 9, 30, 0).unwrap()
+        );
+        assert_eq!(Value::from(AssumedOffset::<10800>(datetime)), value);
+
+        let AssumedOffset(datetime) = from_value::<AssumedOffset<-3600>>(value);
+        assert_eq!(
+            datetime,
+            Utc.with_ymd_and_hms(2019, 11, 27, 13, 30, 0).unwrap()
+        );
+
+        let value = Value::Date(2019, 11, 27, 12, 30, 0, 0);
+        assert_eq!(
+            from_value_opt::<AssumedOffset<86_400>>(value.clone())
+                .unwrap_err()
+                .0,
+            value
+        );
+    }
+}
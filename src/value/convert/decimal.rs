@@ -7,6 +7,9 @@
 // modified, or distributed except according to those terms.
 
 //! This module implements conversion from/to `Value` for `Decimal` type.
+//!
+//! `Decimal` conversion is strict and rejects `Value::Float`, so that binary floating point noise
+//! never leaks into decimals silently. Use `FloatDecimal` to opt into float conversion.
 
 use rust_decimal::Decimal;
 
@@ -59,13 +62,99 @@ impl From<Decimal> for Value {
     }
 }
 
+/// Use it to convert `Value::Float` to `Decimal`.
+///
+/// Float is converted using `Decimal::from_f64_retain`, i.e. the exact value of the binary float
+/// is used (truncated to `Decimal` precision), so `0.1` becomes
+/// `0.1000000000000000055511151231`. Non-finite floats and floats out of `Decimal` range are
+/// rejected. Other values are converted as `Decimal`.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct FloatDecimal(pub Decimal);
+
+impl ConvIr<FloatDecimal> for ParseIr<FloatDecimal> {
+    fn new(v: Value) -> Result<Self, FromValueError> {
+        match v {
+            Value::Float(x) => match Decimal::from_f64_retain(x) {
+                Some(output) => Ok(ParseIr {
+                    value: Value::Float(x),
+                    output: FloatDecimal(output),
+                }),
+                None => Err(FromValueError(Value::Float(x))),
+            },
+            v => <ParseIr<Decimal>>::new(v).map(|ir| ParseIr {
+                value: ir.value,
+                output: FloatDecimal(ir.output),
+            }),
+        }
+    }
+    fn commit(self) -> FloatDecimal {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl FromValue for FloatDecimal {
+    type Intermediate = ParseIr<FloatDecimal>;
+}
+
+impl From<FloatDecimal> for Value {
+    fn from(x: FloatDecimal) -> Value {
+        x.0.into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
     use rust_decimal::Decimal;
 
+    use std::str::FromStr;
+
     use super::super::*;
 
+    #[test]
+    fn should_preserve_decimal_scale() {
+        let value = Value::from("123.4500");
+        let x = from_value::<Decimal>(value.clone());
+        assert_eq!(x.scale(), 4);
+        assert_eq!(Value::from(x), value);
+
+        assert_eq!(from_value::<Decimal>(Value::Int(-42)), Decimal::from(-42));
+        assert_eq!(
+            from_value::<Decimal>(Value::UInt(u64::MAX)),
+            Decimal::from(u64::MAX)
+        );
+        assert!(from_value_opt::<Decimal>(Value::from("foo")).is_err());
+    }
+
+    #[test]
+    fn should_reject_float_in_strict_mode() {
+        let value = Value::Float(0.1);
+        assert_eq!(
+            from_value_opt::<Decimal>(value.clone()).unwrap_err().0,
+            value
+        );
+    }
+
+    #[test]
+    fn should_retain_exact_float_value() {
+        let FloatDecimal(x) = from_value(Value::Float(0.1));
+        assert_eq!(x.to_string(), "0.1000000000000000055511151231");
+        assert_ne!(x, Decimal::from_str("0.1").unwrap());
+
+        let FloatDecimal(x) = from_value(Value::Float(1e20));
+        assert_eq!(x, Decimal::from_str("100000000000000000000").unwrap());
+
+        let FloatDecimal(x) = from_value(Value::from("0.1"));
+        assert_eq!(x, Decimal::from_str("0.1").unwrap());
+
+        for x in &[f64::NAN, f64::INFINITY, 1e40] {
+            assert!(from_value_opt::<FloatDecimal>(Value::Float(*x)).is_err());
+        }
+    }
+
     proptest! {
         #[test]
         fn decimal_roundtrip(
@@ -0,0 +1,513 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+//! This module implements conversions from/to `Value` for textual duration formats.
+
+use lexical::parse;
+use regex::bytes::Regex;
+use time;
+
+use std::time::Duration;
+
+use super::{ConvIr, FromValue, FromValueError, ParseIr, Value};
+
+lazy_static! {
+    static ref ISO_DURATION_RE: Regex = Regex::new(
+        r"^(-)?P(?:([0-9]+)W)?(?:([0-9]+)D)?(?:T(?:([0-9]+)H)?(?:([0-9]+)M)?(?:([0-9]+)(?:[.,]([0-9]{1,9}))?S)?)?$"
+    )
+    .unwrap();
+}
+
+/// Use it to parse ISO 8601 durations, such as `"PT1H30M"` or `"-P1DT2H3M4.5S"`.
+///
+/// Supported designators are weeks (`W`), days (`D`), hours (`H`), minutes (`M`) and seconds
+/// (`S`) with up to nine fractional digits. Years and months are not supported because their
+/// length is not fixed. Leading `-` denotes a negative duration.
+///
+/// `From<IsoDuration> for Value` emits canonical ISO 8601 representation (e.g. `"P1DT2H"`).
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct IsoDuration(pub time::Duration);
+
+fn parse_iso_duration(bytes: &[u8]) -> Option<time::Duration> {
+    let captures = ISO_DURATION_RE.captures(bytes)?;
+
+    // Reject designators without components, i.e. "P", "PT" or "P1DT".
+    let has_time_part = bytes.contains(&b'T');
+    let first_component = if has_time_part { 4 } else { 2 };
+    if (first_component..=6).all(|i| captures.get(i).is_none()) {
+        return None;
+    }
+
+    let component = |i: usize, multiplier: i64| -> Option<i64> {
+        match captures.get(i) {
+            Some(x) => parse::<i64, _>(x.as_bytes()).ok()?.checked_mul(multiplier),
+            None => Some(0),
+        }
+    };
+
+    let mut secs = 0_i64;
+    for &(i, multiplier) in &[
+        (2, 7 * 24 * 3600),
+        (3, 24 * 3600),
+        (4, 3600),
+        (5, 60),
+        (6, 1),
+    ] {
+        secs = secs.checked_add(component(i, multiplier)?)?;
+    }
+    if secs > time::Duration::max_value().num_seconds() - 1 {
+        return None;
+    }
+    let nanos = match captures.get(7) {
+        Some(x) => {
+            let fraction = x.as_bytes();
+            parse::<i64, _>(fraction).ok()? * 10_i64.pow(9 - fraction.len() as u32)
+        }
+        None => 0,
+    };
+
+    let duration = time::Duration::seconds(secs) + time::Duration::nanoseconds(nanos);
+    if captures.get(1).is_some() {
+        Some(-duration)
+    } else {
+        Some(duration)
+    }
+}
+
+impl ConvIr<IsoDuration> for ParseIr<IsoDuration> {
+    fn new(v: Value) -> Result<ParseIr<IsoDuration>, FromValueError> {
+        let output = match v {
+            Value::Bytes(ref bytes) => parse_iso_duration(bytes),
+            _ => None,
+        };
+        match output {
+            Some(output) => Ok(ParseIr {
+                value: v,
+                output: IsoDuration(output),
+            }),
+            None => Err(FromValueError(v)),
+        }
+    }
+    fn commit(self) -> IsoDuration {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl FromValue for IsoDuration {
+    type Intermediate = ParseIr<IsoDuration>;
+}
+
+impl From<IsoDuration> for Value {
+    fn from(IsoDuration(mut x): IsoDuration) -> Value {
+        let mut output = String::new();
+        if x < time::Duration::zero() {
+            output.push('-');
+            x = -x;
+        }
+        output.push('P');
+
+        let days = x.num_days();
+        x = x - time::Duration::days(days);
+        let hours = x.num_hours();
+        x = x - time::Duration::hours(hours);
+        let minutes = x.num_minutes();
+        x = x - time::Duration::minutes(minutes);
+        let seconds = x.num_seconds();
+        x = x - time::Duration::seconds(seconds);
+        let nanos = x.num_nanoseconds().unwrap_or(0);
+
+        if days > 0 {
+            output.push_str(&format!("{}D", days));
+        }
+        if hours > 0 || minutes > 0 || seconds > 0 || nanos > 0 || days == 0 {
+            output.push('T');
+            if hours > 0 {
+                output.push_str(&format!("{}H", hours));
+            }
+            if minutes > 0 {
+                output.push_str(&format!("{}M", minutes));
+            }
+            if nanos > 0 {
+                let fraction = format!("{:09}", nanos);
+                output.push_str(&format!("{}.{}S", seconds, fraction.trim_end_matches('0')));
+            } else if seconds > 0 || (hours == 0 && minutes == 0) {
+                output.push_str(&format!("{}S", seconds));
+            }
+        }
+
+        Value::Bytes(output.into_bytes())
+    }
+}
+
+/// Use it to parse Go-style duration strings, such as `"1h30m"`, `"500ms"` or `"1.5h"`.
+///
+/// String is a sequence of decimal numbers, each with optional fraction and a unit suffix.
+/// Valid units are `ns`, `us` (or `µs`), `ms`, `s`, `m` and `h`. Special string `"0"` is
+/// also accepted. Negative durations are not supported.
+///
+/// `From<HumanDuration> for Value` emits canonical form (e.g. `"1h30m0s"`, `"500ms"`).
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct HumanDuration(pub Duration);
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+fn parse_human_duration(bytes: &[u8]) -> Option<Duration> {
+    const UNITS: &[(&str, u128)] = &[
+        ("ns", 1),
+        ("us", 1_000),
+        ("\u{b5}s", 1_000),
+        ("\u{3bc}s", 1_000),
+        ("ms", 1_000_000),
+        ("s", NANOS_PER_SEC),
+        ("m", 60 * NANOS_PER_SEC),
+        ("h", 3600 * NANOS_PER_SEC),
+    ];
+
+    if bytes == b"0" {
+        return Some(Duration::from_secs(0));
+    }
+    if bytes.is_empty() {
+        return None;
+    }
+
+    let mut input = bytes;
+    let mut nanos = 0_u128;
+    while !input.is_empty() {
+        let int_len = input.iter().take_while(|x| x.is_ascii_digit()).count();
+        let (int, rest) = input.split_at(int_len);
+        let (frac, rest) = match rest.split_first() {
+            Some((b'.', rest)) => {
+                let frac_len = rest.iter().take_while(|x| x.is_ascii_digit()).count();
+                rest.split_at(frac_len)
+            }
+            _ => (&[][..], rest),
+        };
+        if int.is_empty() && frac.is_empty() {
+            return None;
+        }
+
+        let unit_len = rest
+            .iter()
+            .take_while(|x| !x.is_ascii_digit() && **x != b'.')
+            .count();
+        let (unit, rest) = rest.split_at(unit_len);
+        let &(_, multiplier) = UNITS.iter().find(|(name, _)| name.as_bytes() == unit)?;
+
+        let int = if int.is_empty() {
+            0
+        } else {
+            parse::<u128, _>(int).ok()?
+        };
+        nanos = nanos.checked_add(int.checked_mul(multiplier)?)?;
+        let mut scale = 1;
+        for digit in frac.iter().take(18) {
+            scale *= 10;
+            nanos = nanos.checked_add(u128::from(digit - b'0') * multiplier / scale)?;
+        }
+        input = rest;
+    }
+
+    let secs = nanos / NANOS_PER_SEC;
+    if secs > u128::from(u64::MAX) {
+        return None;
+    }
+    Some(Duration::new(secs as u64, (nanos % NANOS_PER_SEC) as u32))
+}
+
+/// Formats `int.frac` where `frac` has `digits` digits, trailing zeros trimmed.
+fn format_fraction(output: &mut String, int: u128, frac: u128, digits: usize) {
+    output.push_str(&int.to_string());
+    if frac > 0 {
+        let frac = format!("{:0width$}", frac, width = digits);
+        output.push('.');
+        output.push_str(frac.trim_end_matches('0'));
+    }
+}
+
+impl ConvIr<HumanDuration> for ParseIr<HumanDuration> {
+    fn new(v: Value) -> Result<ParseIr<HumanDuration>, FromValueError> {
+        let output = match v {
+            Value::Bytes(ref bytes) => parse_human_duration(bytes),
+            _ => None,
+        };
+        match output {
+            Some(output) => Ok(ParseIr {
+                value: v,
+                output: HumanDuration(output),
+            }),
+            None => Err(FromValueError(v)),
+        }
+    }
+    fn commit(self) -> HumanDuration {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl FromValue for HumanDuration {
+    type Intermediate = ParseIr<HumanDuration>;
+}
+
+impl From<HumanDuration> for Value {
+    fn from(HumanDuration(x): HumanDuration) -> Value {
+        let nanos = x.as_nanos();
+        let mut output = String::new();
+
+        if nanos == 0 {
+            output.push_str("0s");
+        } else if nanos < 1_000 {
+            output.push_str(&format!("{}ns", nanos));
+        } else if nanos < 1_000_000 {
+            format_fraction(&mut output, nanos / 1_000, nanos % 1_000, 3);
+            output.push_str("us");
+        } else if nanos < NANOS_PER_SEC {
+            format_fraction(&mut output, nanos / 1_000_000, nanos % 1_000_000, 6);
+            output.push_str("ms");
+        } else {
+            let secs = nanos / NANOS_PER_SEC;
+            let (hours, minutes) = (secs / 3600, secs / 60 % 60);
+            if hours > 0 {
+                output.push_str(&format!("{}h", hours));
+            }
+            if hours > 0 || minutes > 0 {
+                output.push_str(&format!("{}m", minutes));
+            }
+            format_fraction(&mut output, secs % 60, nanos % NANOS_PER_SEC, 9);
+            output.push('s');
+        }
+
+        Value::Bytes(output.into_bytes())
+    }
+}
+
+/// Use it to read durations stored as integer number of seconds.
+///
+/// Accepts non-negative `Value::Int`, `Value::UInt` and bytes parsed as `u64` (integers are sent
+/// as bytes by the text protocol). Negative values are rejected.
+///
+/// `From<DurationSecs> for Value` emits `Value::UInt` of whole seconds (fractional part
+/// is truncated).
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct DurationSecs(pub Duration);
+
+impl ConvIr<DurationSecs> for ParseIr<DurationSecs> {
+    fn new(v: Value) -> Result<ParseIr<DurationSecs>, FromValueError> {
+        let secs = match v {
+            Value::Int(x) if x >= 0 => x as u64,
+            Value::UInt(x) => x,
+            Value::Bytes(ref bytes) => match parse::<u64, _>(bytes) {
+                Ok(x) => x,
+                Err(_) => return Err(FromValueError(v)),
+            },
+            v => return Err(FromValueError(v)),
+        };
+        Ok(ParseIr {
+            value: v,
+            output: DurationSecs(Duration::from_secs(secs)),
+        })
+    }
+    fn commit(self) -> DurationSecs {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl FromValue for DurationSecs {
+    type Intermediate = ParseIr<DurationSecs>;
+}
+
+impl From<DurationSecs> for Value {
+    fn from(x: DurationSecs) -> Value {
+        Value::UInt(x.0.as_secs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::Duration;
+
+    use super::super::*;
+
+    #[test]
+    fn should_parse_iso_duration() {
+        for (input, expected) in &[
+            ("PT1H30M", Duration::minutes(90)),
+            (
+                "P1DT2H3M4S",
+                Duration::days(1)
+                    + Duration::hours(2)
+                    + Duration::minutes(3)
+                    + Duration::seconds(4),
+            ),
+            ("-PT1H", -Duration::hours(1)),
+            ("PT1.5S", Duration::milliseconds(1500)),
+            ("PT0.000000001S", Duration::nanoseconds(1)),
+            ("P1W", Duration::weeks(1)),
+            ("P2D", Duration::days(2)),
+            ("PT0S", Duration::zero()),
+        ] {
+            let IsoDuration(duration) = from_value(Value::from(*input));
+            assert_eq!(duration, *expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn should_reject_malformed_iso_duration() {
+        for input in &[
+            "",
+            "P",
+            "PT",
+            "P1DT",
+            "1H",
+            "P1H",
+            "PT1D",
+            "P1Y",
+            "P1M",
+            "PT1.S",
+            "+PT1H",
+            "PT1H ",
+            "P99999999999999999999D",
+            // U+0663 ARABIC-INDIC DIGIT THREE is matched by Unicode-aware `\d`
+            "PT\u{663}H",
+            "PT1.\u{663}S",
+        ] {
+            let value = Value::from(*input);
+            assert_eq!(
+                from_value_opt::<IsoDuration>(value.clone()).unwrap_err().0,
+                value,
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn should_emit_canonical_iso_duration() {
+        for (duration, expected) in &[
+            (Duration::minutes(90), "PT1H30M"),
+            (Duration::days(1) + Duration::seconds(4), "P1DT4S"),
+            (Duration::days(2), "P2D"),
+            (-Duration::hours(1), "-PT1H"),
+            (Duration::milliseconds(1500), "PT1.5S"),
+            (Duration::zero(), "PT0S"),
+        ] {
+            let value = Value::from(IsoDuration(*duration));
+            assert_eq!(value, Value::from(*expected));
+            assert_eq!(from_value::<IsoDuration>(value), IsoDuration(*duration));
+        }
+    }
+
+    #[test]
+    fn should_parse_human_duration() {
+        use std::time::Duration;
+
+        for (input, expected) in &[
+            ("1h30m", Duration::from_secs(5400)),
+            ("500ms", Duration::from_millis(500)),
+            ("2s", Duration::from_secs(2)),
+            ("1.5h", Duration::from_secs(5400)),
+            ("1m0.5s", Duration::from_millis(60_500)),
+            ("1h1h", Duration::from_secs(7200)),
+            ("10us", Duration::from_micros(10)),
+            ("10\u{b5}s", Duration::from_micros(10)),
+            ("1ns", Duration::from_nanos(1)),
+            (".5s", Duration::from_millis(500)),
+            ("0", Duration::from_secs(0)),
+        ] {
+            let HumanDuration(duration) = from_value(Value::from(*input));
+            assert_eq!(duration, *expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn should_reject_malformed_human_duration() {
+        for input in &[
+            "",
+            "1",
+            "1x",
+            "h",
+            "1h 30m",
+            "-1s",
+            "1.s.",
+            ".s",
+            "1..5s",
+            "99999999999999999999999999h",
+        ] {
+            let value = Value::from(*input);
+            assert_eq!(
+                from_value_opt::<HumanDuration>(value.clone())
+                    .unwrap_err()
+                    .0,
+                value,
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn should_emit_canonical_human_duration() {
+        use std::time::Duration;
+
+        for (duration, expected) in &[
+            (Duration::from_secs(5400), "1h30m0s"),
+            (Duration::from_millis(500), "500ms"),
+            (Duration::from_millis(1500), "1.5s"),
+            (Duration::from_micros(1500), "1.5ms"),
+            (Duration::from_nanos(1500), "1.5us"),
+            (Duration::from_nanos(1), "1ns"),
+            (Duration::from_secs(61), "1m1s"),
+            (Duration::from_secs(0), "0s"),
+        ] {
+            let value = Value::from(HumanDuration(*duration));
+            assert_eq!(value, Value::from(*expected));
+            assert_eq!(from_value::<HumanDuration>(value), HumanDuration(*duration));
+        }
+    }
+
+    #[test]
+    fn should_read_duration_secs() {
+        use std::time::Duration;
+
+        for (input, expected) in &[
+            (Value::Int(90), 90),
+            (Value::UInt(u64::MAX), u64::MAX),
+            (Value::from("3600"), 3600),
+            (Value::Int(0), 0),
+        ] {
+            let DurationSecs(duration) = from_value(input.clone());
+            assert_eq!(duration, Duration::from_secs(*expected));
+        }
+        assert_eq!(
+            Value::from(DurationSecs(Duration::from_millis(1500))),
+            Value::UInt(1)
+        );
+    }
+
+    #[test]
+    fn should_reject_negative_duration_secs() {
+        for value in &[
+            Value::Int(-1),
+            Value::from("-1"),
+            Value::from("1.5"),
+            Value::NULL,
+        ] {
+            assert_eq!(
+                from_value_opt::<DurationSecs>(value.clone()).unwrap_err().0,
+                *value
+            );
+        }
+    }
+}
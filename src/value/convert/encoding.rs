@@ -0,0 +1,151 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+//! This module implements conversion from/to `Value` for binary data stored as text
+//! (see `Encoded`).
+
+use data_encoding::{Encoding, BASE32, BASE64URL, HEXLOWER_PERMISSIVE};
+
+use std::marker::PhantomData;
+
+use super::{ConvIr, FromValue, FromValueError, ParseIr, Value};
+
+/// Text encoding of binary data used by `Encoded`.
+pub trait Alphabet {
+    /// Returns encoding used to decode and encode values.
+    fn encoding() -> Encoding;
+}
+
+/// Base16 alphabet. Decoding is case-insensitive, encoding produces lowercase digits.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash, Default)]
+pub struct Base16;
+
+impl Alphabet for Base16 {
+    fn encoding() -> Encoding {
+        HEXLOWER_PERMISSIVE
+    }
+}
+
+/// Base32 alphabet of RFC 4648. Padding is required.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash, Default)]
+pub struct Base32;
+
+impl Alphabet for Base32 {
+    fn encoding() -> Encoding {
+        BASE32
+    }
+}
+
+/// URL-safe base64 alphabet of RFC 4648. Padding is required.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash, Default)]
+pub struct Base64Url;
+
+impl Alphabet for Base64Url {
+    fn encoding() -> Encoding {
+        BASE64URL
+    }
+}
+
+/// Binary data stored as text encoded using alphabet `A` (`data-encoding` feature).
+///
+/// MySql bytes are decoded using `A`, conversion to `Value` encodes the data back.
+///
+/// ```
+/// use mysql_common::value::{convert::{from_value, Base32, Encoded}, Value};
+///
+/// let token = from_value::<Encoded<Base32>>(Value::from("MZXW6==="));
+/// assert_eq!(token.0, b"foo");
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct Encoded<A>(pub Vec<u8>, PhantomData<A>);
+
+impl<A> Encoded<A> {
+    /// Wraps `data`.
+    pub fn new(data: Vec<u8>) -> Encoded<A> {
+        Encoded(data, PhantomData)
+    }
+
+    /// Unwraps decoded data.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl<A: Alphabet> ConvIr<Encoded<A>> for ParseIr<Encoded<A>> {
+    fn new(value: Value) -> Result<ParseIr<Encoded<A>>, FromValueError> {
+        let data = match value {
+            Value::Bytes(ref bytes) => A::encoding().decode(bytes).ok(),
+            _ => None,
+        };
+
+        match data {
+            Some(data) => Ok(ParseIr {
+                value,
+                output: Encoded::new(data),
+            }),
+            None => Err(FromValueError(value)),
+        }
+    }
+    fn commit(self) -> Encoded<A> {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl<A: Alphabet> FromValue for Encoded<A> {
+    type Intermediate = ParseIr<Encoded<A>>;
+}
+
+impl<A: Alphabet> From<Encoded<A>> for Value {
+    fn from(x: Encoded<A>) -> Value {
+        Value::Bytes(A::encoding().encode(&x.0).into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Base16, Base32, Base64Url, Encoded};
+    use crate::value::convert::{from_value, from_value_opt};
+    use crate::value::Value;
+
+    #[test]
+    fn should_decode_base32() {
+        let value = Value::from("MZXW6YTBOI======");
+        let token = from_value::<Encoded<Base32>>(value.clone());
+        assert_eq!(token.0, b"foobar");
+        assert_eq!(Value::from(token), value);
+    }
+
+    #[test]
+    fn should_decode_other_alphabets() {
+        let token = from_value::<Encoded<Base16>>(Value::from("DEADbeef"));
+        assert_eq!(token.0, [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(Value::from(token), Value::from("deadbeef"));
+
+        let token = from_value::<Encoded<Base64Url>>(Value::from("-_8="));
+        assert_eq!(token.into_inner(), [0xfb, 0xff]);
+    }
+
+    #[test]
+    fn should_reject_invalid_input() {
+        for input in &["MZXW6!==", "MZXW6", "mzxw6==="] {
+            let value = Value::from(*input);
+            assert_eq!(
+                from_value_opt::<Encoded<Base32>>(value.clone())
+                    .unwrap_err()
+                    .0,
+                value
+            );
+        }
+        assert!(from_value_opt::<Encoded<Base16>>(Value::from("abc")).is_err());
+        assert!(from_value_opt::<Encoded<Base64Url>>(Value::from("+/8=")).is_err());
+        assert!(from_value_opt::<Encoded<Base32>>(Value::Int(1)).is_err());
+    }
+}
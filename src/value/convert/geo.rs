@@ -0,0 +1,132 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+//! This module implements conversion from/to `Value` for spatial `POINT` values.
+
+use byteorder::{BigEndian as BE, ByteOrder, LittleEndian as LE};
+
+use super::{ConvIr, FromValue, FromValueError, ParseIr, Value};
+
+/// WKB geometry type of a point.
+const WKB_POINT: u32 = 1;
+
+/// Point read from MySql `POINT` value (`geo` feature).
+///
+/// MySql stores geometry as a 4-byte little-endian SRID followed by WKB. SRID is ignored,
+/// both WKB byte orders are accepted. Other geometry types and malformed values are rejected.
+/// `From<Point> for Value` emits SRID `0` followed by little-endian WKB.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Default)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+fn parse_point<B: ByteOrder>(wkb: &[u8]) -> Option<Point> {
+    if B::read_u32(&wkb[..4]) == WKB_POINT {
+        Some(Point {
+            x: B::read_f64(&wkb[4..12]),
+            y: B::read_f64(&wkb[12..]),
+        })
+    } else {
+        None
+    }
+}
+
+impl ConvIr<Point> for ParseIr<Point> {
+    fn new(value: Value) -> Result<ParseIr<Point>, FromValueError> {
+        let point = match value {
+            // SRID, byte order, geometry type, x, y
+            Value::Bytes(ref bytes) if bytes.len() == 4 + 1 + 4 + 8 + 8 => match bytes[4] {
+                0x00 => parse_point::<BE>(&bytes[5..]),
+                0x01 => parse_point::<LE>(&bytes[5..]),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        match point {
+            Some(output) => Ok(ParseIr { value, output }),
+            None => Err(FromValueError(value)),
+        }
+    }
+    fn commit(self) -> Point {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl FromValue for Point {
+    type Intermediate = ParseIr<Point>;
+}
+
+impl From<Point> for Value {
+    fn from(x: Point) -> Value {
+        let mut bytes = vec![0; 25];
+        bytes[4] = 0x01;
+        LE::write_u32(&mut bytes[5..9], WKB_POINT);
+        LE::write_f64(&mut bytes[9..17], x.x);
+        LE::write_f64(&mut bytes[17..], x.y);
+        Value::Bytes(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Point;
+    use crate::value::convert::{from_value, from_value_opt};
+    use crate::value::Value;
+
+    // SELECT ST_GeomFromText('POINT(1 2)', 4326)
+    const POINT_1_2: &[u8] = b"\xe6\x10\x00\x00\x01\x01\x00\x00\x00\
+        \x00\x00\x00\x00\x00\x00\xf0\x3f\
+        \x00\x00\x00\x00\x00\x00\x00\x40";
+
+    #[test]
+    fn should_parse_wkb_point() {
+        let point = from_value::<Point>(Value::Bytes(POINT_1_2.to_vec()));
+        assert_eq!(point, Point { x: 1.0, y: 2.0 });
+
+        let big_endian = b"\x00\x00\x00\x00\x00\x00\x00\x00\x01\
+            \x3f\xf0\x00\x00\x00\x00\x00\x00\
+            \x40\x00\x00\x00\x00\x00\x00\x00";
+        let point = from_value::<Point>(Value::Bytes(big_endian.to_vec()));
+        assert_eq!(point, Point { x: 1.0, y: 2.0 });
+    }
+
+    #[test]
+    fn should_roundtrip_point() {
+        let value = Value::from(Point { x: 1.0, y: 2.0 });
+        let mut expected = POINT_1_2.to_vec();
+        expected[..4].copy_from_slice(&[0; 4]);
+        assert_eq!(value, Value::Bytes(expected));
+        assert_eq!(from_value::<Point>(value), Point { x: 1.0, y: 2.0 });
+    }
+
+    #[test]
+    fn should_reject_malformed_point() {
+        let mut linestring = POINT_1_2.to_vec();
+        linestring[5] = 0x02;
+        let mut bad_byte_order = POINT_1_2.to_vec();
+        bad_byte_order[4] = 0x02;
+
+        for value in &[
+            Value::Bytes(linestring),
+            Value::Bytes(bad_byte_order),
+            Value::Bytes(POINT_1_2[..24].to_vec()),
+            Value::Bytes(Vec::new()),
+            Value::Int(1),
+        ] {
+            assert_eq!(
+                from_value_opt::<Point>(value.clone()).unwrap_err().0,
+                *value
+            );
+        }
+    }
+}
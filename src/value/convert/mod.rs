@@ -6,7 +6,9 @@
 // option. All files in the project carrying such notice may not be copied,
 // modified, or distributed except according to those terms.
 
-use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use chrono::{
+    DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc,
+};
 use lexical::parse;
 use num_traits::{FromPrimitive, ToPrimitive};
 use regex::bytes::Regex;
@@ -14,6 +16,8 @@ use time::{self, at, strptime, Timespec, Tm};
 use uuid::Uuid;
 
 use std::any::type_name;
+use std::borrow::Cow;
+use std::convert::TryFrom;
 use std::error::Error;
 use std::fmt;
 use std::str::from_utf8;
@@ -23,7 +27,47 @@ use crate::value::Value;
 
 mod bigdecimal;
 mod bigint;
+mod boolean;
+mod borrowed;
+mod datetime;
 mod decimal;
+mod duration;
+#[cfg(feature = "data-encoding")]
+mod encoding;
+#[cfg(feature = "geo")]
+mod geo;
+mod net;
+mod number;
+mod presence;
+mod string;
+#[cfg(feature = "time03")]
+mod time03;
+#[cfg(feature = "chrono-tz")]
+mod timezone;
+#[cfg(feature = "semver")]
+mod version;
+
+pub use self::boolean::{CBool, SqlBool, YnBool};
+pub use self::borrowed::FromValueRef;
+pub use self::datetime::{
+    AssumedOffset, EpochNanos, LocaleTime, LocaleTimeIr, NamedMonthDate, PrecisionError,
+    ZeroDateAsNone,
+};
+pub use self::decimal::FloatDecimal;
+pub use self::duration::{DurationSecs, HumanDuration, IsoDuration};
+#[cfg(feature = "data-encoding")]
+pub use self::encoding::{Alphabet, Base16, Base32, Base64Url, Encoded};
+#[cfg(feature = "geo")]
+pub use self::geo::Point;
+pub use self::number::{
+    AllowNonFinite, BeU64, Bits, Bounded, CleanedNumber, CleanedNumberIr, Currency, LeU64,
+    MinorUnits, NumberCleaner, SciInt, Snowflake,
+};
+pub use self::presence::{presence, Presence};
+pub use self::string::{
+    CStrBytes, CStrBytesIr, CsvField, CsvFieldIr, DelimitedList, NonEmptyString, NonEmptyStringIr,
+    UuidStr,
+};
 
 lazy_static! {
     static ref DATETIME_RE_YMD: Regex = { Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap() };
@@ -31,12 +75,8 @@ lazy_static! {
         { Regex::new(r"^\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}$").unwrap() };
     static ref DATETIME_RE_YMD_HMS_NS: Regex =
         { Regex::new(r"^\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d{1,6}$").unwrap() };
-    static ref TIME_RE_HH_MM_SS: Regex = { Regex::new(r"^\d{2}:[0-5]\d:[0-5]\d$").unwrap() };
-    static ref TIME_RE_HH_MM_SS_MS: Regex =
-        { Regex::new(r"^\d{2}:[0-5]\d:[0-5]\d\.\d{1,6}$").unwrap() };
-    static ref TIME_RE_HHH_MM_SS: Regex = { Regex::new(r"^[0-8]\d\d:[0-5]\d:[0-5]\d$").unwrap() };
-    static ref TIME_RE_HHH_MM_SS_MS: Regex =
-        { Regex::new(r"^[0-8]\d\d:[0-5]\d:[0-5]\d\.\d{1,6}$").unwrap() };
+    static ref TIME_RE: Regex =
+        { Regex::new(r"^([0-9]{2,}):([0-5][0-9]):([0-5][0-9])(?:\.([0-9]{1,6}))?$").unwrap() };
 }
 
 /// `FromValue` conversion error.
@@ -59,6 +99,71 @@ impl Error for FromValueError {
     }
 }
 
+/// `FromValue` conversion error that names the target type and, if possible, explains the
+/// failure (see `FromValue::from_value_explained` and `FromValue::get_intermediate_explained`).
+///
+/// Converts into `FromValueError` holding the same value.
+#[derive(Debug)]
+pub struct ConversionError {
+    value: Value,
+    type_name: &'static str,
+    reason: Option<&'static str>,
+}
+
+impl ConversionError {
+    /// Creates an error for `value` that couldn't be converted to `T`.
+    ///
+    /// The reason is taken from `ConvIr::explain` of `T::Intermediate`.
+    pub fn new<T: FromValue>(value: Value) -> ConversionError {
+        ConversionError {
+            reason: <T::Intermediate as ConvIr<T>>::explain(&value),
+            type_name: type_name::<T>(),
+            value,
+        }
+    }
+
+    /// Returns the value that couldn't be converted.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Returns the name of the type the value couldn't be converted to.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns explanation of the failure, if any.
+    pub fn reason(&self) -> Option<&'static str> {
+        self.reason
+    }
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Couldn't convert the value `{:?}` to `{}`",
+            self.value, self.type_name
+        )?;
+        match self.reason {
+            Some(reason) => write!(f, ": {}", reason),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Error for ConversionError {
+    fn description(&self) -> &str {
+        "Couldn't convert the value to a desired type"
+    }
+}
+
+impl From<ConversionError> for FromValueError {
+    fn from(err: ConversionError) -> FromValueError {
+        FromValueError(err.value)
+    }
+}
+
 /// Basic operations on `FromValue` conversion intermediate result.
 ///
 /// See [`FromValue`](trait.FromValue.html)
@@ -66,6 +171,13 @@ pub trait ConvIr<T>: Sized {
     fn new(v: Value) -> Result<Self, FromValueError>;
     fn commit(self) -> T;
     fn rollback(self) -> Value;
+
+    /// Explains why `new` rejected `value`, if there is anything to add to "couldn't convert".
+    ///
+    /// Only called for values rejected by `new`. Default implementation returns `None`.
+    fn explain(_value: &Value) -> Option<&'static str> {
+        None
+    }
 }
 
 /// Implement this trait to convert value to something.
@@ -120,10 +232,22 @@ pub trait FromValue: Sized {
         Ok(ir.commit())
     }
 
+    /// Will return `Err(ConversionError)` naming `Self` if could not convert `v` to `Self`.
+    fn from_value_explained(v: Value) -> Result<Self, ConversionError> {
+        let ir = Self::get_intermediate_explained(v)?;
+        Ok(ir.commit())
+    }
+
     /// Will return `Err(Error::FromValueError(v))` if `v` is not convertible to `Self`.
     fn get_intermediate(v: Value) -> Result<Self::Intermediate, FromValueError> {
         Self::Intermediate::new(v)
     }
+
+    /// Will return `Err(ConversionError)` naming `Self` if `v` is not convertible to `Self`.
+    fn get_intermediate_explained(v: Value) -> Result<Self::Intermediate, ConversionError> {
+        Self::get_intermediate(v)
+            .map_err(|FromValueError(value)| ConversionError::new::<Self>(value))
+    }
 }
 
 /// Will panic if could not convert `v` to `T`
@@ -236,6 +360,9 @@ where
             },
         }
     }
+    fn explain(value: &Value) -> Option<&'static str> {
+        Ir::explain(value)
+    }
 }
 
 impl<T> FromValue for Option<T>
@@ -293,6 +420,30 @@ impl ConvIr<String> for StringIr {
     }
 }
 
+impl ConvIr<Box<str>> for StringIr {
+    fn new(v: Value) -> Result<StringIr, FromValueError> {
+        <StringIr as ConvIr<String>>::new(v)
+    }
+    fn commit(self) -> Box<str> {
+        <StringIr as ConvIr<String>>::commit(self).into_boxed_str()
+    }
+    fn rollback(self) -> Value {
+        Value::Bytes(self.bytes)
+    }
+}
+
+impl ConvIr<Cow<'static, str>> for StringIr {
+    fn new(v: Value) -> Result<StringIr, FromValueError> {
+        <StringIr as ConvIr<String>>::new(v)
+    }
+    fn commit(self) -> Cow<'static, str> {
+        Cow::Owned(<StringIr as ConvIr<String>>::commit(self))
+    }
+    fn rollback(self) -> Value {
+        Value::Bytes(self.bytes)
+    }
+}
+
 /// Intermediate result of a Value-to-Integer conversion.
 #[derive(Debug)]
 pub struct ParseIr<T> {
@@ -358,6 +509,11 @@ impl ConvIr<u64> for ParseIr<u64> {
     }
 }
 
+/// Reason of rejecting `"inf"`, `"nan"` (or out of range) bytes as a float
+/// (see `AllowNonFinite`).
+const NON_FINITE: &str = "non-finite float";
+
+/// Bytes are rejected if parsed to a non-finite value (use `AllowNonFinite` to accept them).
 impl ConvIr<f32> for ParseIr<f32> {
     fn new(v: Value) -> Result<ParseIr<f32>, FromValueError> {
         match v {
@@ -370,13 +526,13 @@ impl ConvIr<f32> for ParseIr<f32> {
                 })
             }
             Value::Bytes(bytes) => {
-                let val = parse(&*bytes).ok();
+                let val = parse::<f32, _>(&*bytes).ok();
                 match val {
-                    Some(x) => Ok(ParseIr {
+                    Some(x) if x.is_finite() => Ok(ParseIr {
                         value: Value::Bytes(bytes),
                         output: x,
                     }),
-                    None => Err(FromValueError(Value::Bytes(bytes))),
+                    _ => Err(FromValueError(Value::Bytes(bytes))),
                 }
             }
             v => Err(FromValueError(v)),
@@ -388,8 +544,18 @@ impl ConvIr<f32> for ParseIr<f32> {
     fn rollback(self) -> Value {
         self.value
     }
+    fn explain(value: &Value) -> Option<&'static str> {
+        match *value {
+            Value::Bytes(ref bytes) => match parse::<f32, _>(bytes) {
+                Ok(x) if !x.is_finite() => Some(NON_FINITE),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
 }
 
+/// Bytes are rejected if parsed to a non-finite value (use `AllowNonFinite` to accept them).
 impl ConvIr<f64> for ParseIr<f64> {
     fn new(v: Value) -> Result<ParseIr<f64>, FromValueError> {
         match v {
@@ -398,9 +564,9 @@ impl ConvIr<f64> for ParseIr<f64> {
                 output: x,
             }),
             Value::Bytes(bytes) => {
-                let val = parse(&*bytes).ok();
+                let val = parse::<f64, _>(&*bytes).ok();
                 match val {
-                    Some(x) => Ok(ParseIr {
+                    Some(x) if x.is_finite() => Ok(ParseIr {
                         value: Value::Bytes(bytes),
                         output: x,
                     }),
@@ -416,6 +582,15 @@ impl ConvIr<f64> for ParseIr<f64> {
     fn rollback(self) -> Value {
         self.value
     }
+    fn explain(value: &Value) -> Option<&'static str> {
+        match *value {
+            Value::Bytes(ref bytes) => match parse::<f64, _>(bytes) {
+                Ok(x) if !x.is_finite() => Some(NON_FINITE),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
 }
 
 impl ConvIr<bool> for ParseIr<bool> {
@@ -478,6 +653,30 @@ impl ConvIr<Vec<u8>> for BytesIr {
     }
 }
 
+/// Succeeds only if bytes length is exactly `N`.
+impl<const N: usize> ConvIr<[u8; N]> for ParseIr<[u8; N]> {
+    fn new(v: Value) -> Result<ParseIr<[u8; N]>, FromValueError> {
+        let output = match v {
+            Value::Bytes(ref bytes) => <[u8; N]>::try_from(&bytes[..]).ok(),
+            _ => None,
+        };
+        match output {
+            Some(output) => Ok(ParseIr { value: v, output }),
+            None => Err(FromValueError(v)),
+        }
+    }
+    fn commit(self) -> [u8; N] {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl<const N: usize> FromValue for [u8; N] {
+    type Intermediate = ParseIr<[u8; N]>;
+}
+
 impl ConvIr<Timespec> for ParseIr<Timespec> {
     fn new(value: Value) -> Result<ParseIr<Timespec>, FromValueError> {
         let tm_utcoff = at(Timespec::new(0, 0)).tm_utcoff;
@@ -531,6 +730,9 @@ impl ConvIr<Timespec> for ParseIr<Timespec> {
     }
 }
 
+/// Reason of rejecting MySql zero date (see `Value::is_zero_date`).
+const ZERO_DATE: &str = "zero date (`0000-00-00`) has no chrono representation";
+
 impl ConvIr<NaiveDateTime> for ParseIr<NaiveDateTime> {
     fn new(value: Value) -> Result<ParseIr<NaiveDateTime>, FromValueError> {
         let result = match value {
@@ -568,6 +770,48 @@ impl ConvIr<NaiveDateTime> for ParseIr<NaiveDateTime> {
     fn rollback(self) -> Value {
         self.value
     }
+    fn explain(value: &Value) -> Option<&'static str> {
+        if value.is_zero_date() {
+            Some(ZERO_DATE)
+        } else {
+            None
+        }
+    }
+}
+
+/// Value is interpreted as UTC wall-clock time (as `NaiveDateTime`).
+impl ConvIr<DateTime<Utc>> for ParseIr<DateTime<Utc>> {
+    fn new(v: Value) -> Result<ParseIr<DateTime<Utc>>, FromValueError> {
+        let ParseIr { value, output } = ParseIr::<NaiveDateTime>::new(v)?;
+        Ok(ParseIr {
+            value,
+            output: Utc.from_utc_datetime(&output),
+        })
+    }
+    fn commit(self) -> DateTime<Utc> {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+/// Value is interpreted as UTC wall-clock time, so the offset is always `+00:00`
+/// (see `AssumedOffset` to assume another offset).
+impl ConvIr<DateTime<FixedOffset>> for ParseIr<DateTime<FixedOffset>> {
+    fn new(v: Value) -> Result<ParseIr<DateTime<FixedOffset>>, FromValueError> {
+        let ParseIr { value, output } = ParseIr::<DateTime<Utc>>::new(v)?;
+        Ok(ParseIr {
+            value,
+            output: output.into(),
+        })
+    }
+    fn commit(self) -> DateTime<FixedOffset> {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
 }
 
 impl ConvIr<NaiveDate> for ParseIr<NaiveDate> {
@@ -605,25 +849,30 @@ impl ConvIr<NaiveDate> for ParseIr<NaiveDate> {
     fn rollback(self) -> Value {
         self.value
     }
-}
-
-#[inline]
-fn parse_micros(micros_bytes: &[u8]) -> u32 {
-    let mut micros = parse(micros_bytes).unwrap();
-
-    let mut pad_zero_cnt = 0;
-    for b in micros_bytes.iter() {
-        if *b == b'0' {
-            pad_zero_cnt += 1;
+    fn explain(value: &Value) -> Option<&'static str> {
+        if value.is_zero_date() {
+            Some(ZERO_DATE)
         } else {
-            break;
+            None
         }
     }
+}
 
-    for _ in 0..(6 - pad_zero_cnt - (micros_bytes.len() - pad_zero_cnt)) {
+/// Parses fractional part of seconds (at least one digit) scaled to microseconds.
+///
+/// Digits beyond the sixth are ignored (i.e. value is truncated to microseconds). Returns `None`
+/// if there is anything but ASCII digits.
+#[inline]
+fn parse_micros(micros_bytes: &[u8]) -> Option<u32> {
+    if micros_bytes.is_empty() || !micros_bytes.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    let digits = &micros_bytes[..micros_bytes.len().min(6)];
+    let mut micros: u32 = parse(digits).ok()?;
+    for _ in digits.len()..6 {
         micros *= 10;
     }
-    micros
+    Some(micros)
 }
 
 /// Returns (year, month, day, hour, minute, second, micros)
@@ -670,6 +919,11 @@ fn parse_mysql_datetime_string(bytes: &[u8]) -> Option<(u32, u32, u32, u32, u32,
         ),
     };
 
+    let micros = match micros {
+        Some(pos) => parse_micros(&bytes[pos])?,
+        None => 0,
+    };
+
     Some((
         parse(&bytes[year]).unwrap(),
         parse(&bytes[month]).unwrap(),
@@ -677,21 +931,12 @@ fn parse_mysql_datetime_string(bytes: &[u8]) -> Option<(u32, u32, u32, u32, u32,
         hour.map(|pos| parse(&bytes[pos]).unwrap()).unwrap_or(0),
         minute.map(|pos| parse(&bytes[pos]).unwrap()).unwrap_or(0),
         second.map(|pos| parse(&bytes[pos]).unwrap()).unwrap_or(0),
-        micros.map(|pos| parse_micros(&bytes[pos])).unwrap_or(0),
+        micros,
     ))
 }
 
 /// Returns (is_neg, hours, minutes, seconds, microseconds)
 fn parse_mysql_time_string(mut bytes: &[u8]) -> Option<(bool, u32, u32, u32, u32)> {
-    #[derive(PartialEq, Eq, PartialOrd, Ord)]
-    #[repr(u8)]
-    enum TimeKind {
-        HhMmSs = 0,
-        HhhMmSs,
-        HhMmSsMs,
-        HhhMmSsMs,
-    }
-
     if bytes.len() < 8 {
         return None;
     }
@@ -701,36 +946,26 @@ fn parse_mysql_time_string(mut bytes: &[u8]) -> Option<(bool, u32, u32, u32, u32
         bytes = &bytes[1..];
     }
 
-    let len = bytes.len();
-
-    let kind = if len == 8 && TIME_RE_HH_MM_SS.is_match(bytes) {
-        TimeKind::HhMmSs
-    } else if len == 9 && TIME_RE_HHH_MM_SS.is_match(bytes) {
-        TimeKind::HhhMmSs
-    } else if TIME_RE_HH_MM_SS_MS.is_match(bytes) {
-        TimeKind::HhMmSsMs
-    } else if TIME_RE_HHH_MM_SS_MS.is_match(bytes) {
-        TimeKind::HhhMmSsMs
-    } else {
-        return None;
-    };
-
-    let (hour_pos, min_pos, sec_pos, micros_pos) = match kind {
-        TimeKind::HhMmSs => (..2, 3..5, 6..8, None),
-        TimeKind::HhMmSsMs => (..2, 3..5, 6..8, Some(9..)),
-        TimeKind::HhhMmSs => (..3, 4..6, 7..9, None),
-        TimeKind::HhhMmSsMs => (..3, 4..6, 7..9, Some(10..)),
+    let captures = TIME_RE.captures(bytes)?;
+    // hours may have any number of digits, so it may overflow
+    let hours = parse(&captures[1]).ok()?;
+    let micros = match captures.get(4) {
+        Some(micros) => parse_micros(micros.as_bytes())?,
+        None => 0,
     };
 
     Some((
         is_neg,
-        parse(&bytes[hour_pos]).unwrap(),
-        parse(&bytes[min_pos]).unwrap(),
-        parse(&bytes[sec_pos]).unwrap(),
-        micros_pos.map(|pos| parse_micros(&bytes[pos])).unwrap_or(0),
+        hours,
+        parse(&captures[2]).ok()?,
+        parse(&captures[3]).ok()?,
+        micros,
     ))
 }
 
+/// Reason of rejecting negative or day-carrying `TIME` value as `NaiveTime`.
+const NAIVE_TIME_RANGE: &str = "TIME value out of NaiveTime's 0..24h range";
+
 impl ConvIr<NaiveTime> for ParseIr<NaiveTime> {
     fn new(value: Value) -> Result<ParseIr<NaiveTime>, FromValueError> {
         let result = match value {
@@ -766,11 +1001,24 @@ impl ConvIr<NaiveTime> for ParseIr<NaiveTime> {
     fn rollback(self) -> Value {
         self.value
     }
+    fn explain(value: &Value) -> Option<&'static str> {
+        match *value {
+            Value::Time(..) => Some(NAIVE_TIME_RANGE),
+            Value::Bytes(ref bytes) => parse_mysql_time_string(bytes).map(|_| NAIVE_TIME_RANGE),
+            _ => None,
+        }
+    }
 }
 
+/// Reason of rejecting negative `TIME` value as `std::time::Duration`.
+const NEGATIVE_DURATION: &str = "negative TIME value requires `time::Duration`";
+
+/// `std::time::Duration` is unsigned, so negative time (i.e. `Value::Time(true, ..)` or
+/// `"-00:00:01"`) is rejected. Use `time::Duration` to convert negative values.
 impl ConvIr<Duration> for ParseIr<Duration> {
     fn new(v: Value) -> Result<ParseIr<Duration>, FromValueError> {
         match v {
+            Value::Time(true, ..) => Err(FromValueError(v)),
             Value::Time(false, days, hours, minutes, seconds, microseconds) => {
                 let nanos = (microseconds as u32) * 1000;
                 let secs = u64::from(seconds)
@@ -791,7 +1039,8 @@ impl ConvIr<Duration> for ParseIr<Duration> {
                             + u64::from(hours) * 60 * 60;
                         Duration::new(secs, nanos)
                     }
-                    _ => return Err(FromValueError(Value::Bytes(val_bytes))),
+                    Some((true, ..)) => return Err(FromValueError(Value::Bytes(val_bytes))),
+                    None => return Err(FromValueError(Value::Bytes(val_bytes))),
                 };
                 Ok(ParseIr {
                     value: Value::Bytes(val_bytes),
@@ -807,6 +1056,16 @@ impl ConvIr<Duration> for ParseIr<Duration> {
     fn rollback(self) -> Value {
         self.value
     }
+    fn explain(value: &Value) -> Option<&'static str> {
+        match *value {
+            Value::Time(true, ..) => Some(NEGATIVE_DURATION),
+            Value::Bytes(ref bytes) => match parse_mysql_time_string(bytes) {
+                Some((true, ..)) => Some(NEGATIVE_DURATION),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
 }
 
 impl ConvIr<time::Duration> for ParseIr<time::Duration> {
@@ -855,12 +1114,16 @@ impl ConvIr<time::Duration> for ParseIr<time::Duration> {
 }
 
 impl_from_value!(NaiveDateTime, ParseIr<NaiveDateTime>);
+impl_from_value!(DateTime<Utc>, ParseIr<DateTime<Utc>>);
+impl_from_value!(DateTime<FixedOffset>, ParseIr<DateTime<FixedOffset>>);
 impl_from_value!(NaiveDate, ParseIr<NaiveDate>);
 impl_from_value!(NaiveTime, ParseIr<NaiveTime>);
 impl_from_value!(Timespec, ParseIr<Timespec>);
 impl_from_value!(Duration, ParseIr<Duration>);
 impl_from_value!(time::Duration, ParseIr<time::Duration>);
 impl_from_value!(String, StringIr);
+impl_from_value!(Box<str>, StringIr);
+impl_from_value!(Cow<'static, str>, StringIr);
 impl_from_value!(Vec<u8>, BytesIr);
 impl_from_value!(bool, ParseIr<bool>);
 impl_from_value!(i64, ParseIr<i64>);
@@ -888,12 +1151,41 @@ impl<T: Into<Value> + Clone> ToValue for T {
     }
 }
 
+/// Expands a collection into a list of values (e.g. parameters for an `IN` clause).
+///
+/// Each element is converted into a single value, so nested collections are not flattened
+/// (note that `Vec<u8>` expands into integers, use `Value::Bytes` for a single bytes value).
+/// Empty collection yields empty vector.
+pub trait IntoValues {
+    fn into_values(self) -> Vec<Value>;
+}
+
+impl<T: ToValue> IntoValues for &[T] {
+    fn into_values(self) -> Vec<Value> {
+        self.iter().map(ToValue::to_value).collect()
+    }
+}
+
+impl<T: Into<Value>> IntoValues for Vec<T> {
+    fn into_values(self) -> Vec<Value> {
+        self.into_iter().map(Into::into).collect()
+    }
+}
+
+impl<T: Into<Value>, const N: usize> IntoValues for [T; N] {
+    fn into_values(self) -> Vec<Value> {
+        IntoIterator::into_iter(self).map(Into::into).collect()
+    }
+}
+
 impl<'a, T: ToValue> From<&'a T> for Value {
     fn from(x: &'a T) -> Value {
         x.to_value()
     }
 }
 
+/// `None` is `NULL`. Also covers optional references (e.g. `Option<&str>` or `Option<&i32>`),
+/// since references to `ToValue` types are convertible to `Value`.
 impl<T: Into<Value>> From<Option<T>> for Value {
     fn from(x: Option<T>) -> Value {
         match x {
@@ -996,6 +1288,18 @@ impl From<String> for Value {
     }
 }
 
+impl From<Box<str>> for Value {
+    fn from(x: Box<str>) -> Value {
+        Value::Bytes(x.into_boxed_bytes().into())
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for Value {
+    fn from(x: Cow<'a, str>) -> Value {
+        Value::Bytes(x.into_owned().into_bytes())
+    }
+}
+
 impl From<NaiveDateTime> for Value {
     fn from(x: NaiveDateTime) -> Value {
         if 1000 > x.year() || x.year() > 9999 {
@@ -1013,6 +1317,15 @@ impl From<NaiveDateTime> for Value {
     }
 }
 
+/// Value will hold the UTC wall-clock time of `x` (time zone is dropped).
+///
+/// Will panic if the year of UTC wall-clock time is not in range [1000, 9999].
+impl<Tz: TimeZone> From<DateTime<Tz>> for Value {
+    fn from(x: DateTime<Tz>) -> Value {
+        Value::from(x.naive_utc())
+    }
+}
+
 impl From<NaiveDate> for Value {
     fn from(x: NaiveDate) -> Value {
         if 1000 > x.year() || x.year() > 9999 {
@@ -1090,50 +1403,12 @@ impl From<time::Duration> for Value {
     }
 }
 
-macro_rules! from_array_impl {
-    ($n:expr) => {
-        impl From<[u8; $n]> for Value {
-            fn from(x: [u8; $n]) -> Value {
-                Value::from(&x[..])
-            }
-        }
-    };
+impl<const N: usize> From<[u8; N]> for Value {
+    fn from(x: [u8; N]) -> Value {
+        Value::from(&x[..])
+    }
 }
 
-from_array_impl!(0);
-from_array_impl!(1);
-from_array_impl!(2);
-from_array_impl!(3);
-from_array_impl!(4);
-from_array_impl!(5);
-from_array_impl!(6);
-from_array_impl!(7);
-from_array_impl!(8);
-from_array_impl!(9);
-from_array_impl!(10);
-from_array_impl!(11);
-from_array_impl!(12);
-from_array_impl!(13);
-from_array_impl!(14);
-from_array_impl!(15);
-from_array_impl!(16);
-from_array_impl!(17);
-from_array_impl!(18);
-from_array_impl!(19);
-from_array_impl!(20);
-from_array_impl!(21);
-from_array_impl!(22);
-from_array_impl!(23);
-from_array_impl!(24);
-from_array_impl!(25);
-from_array_impl!(26);
-from_array_impl!(27);
-from_array_impl!(28);
-from_array_impl!(29);
-from_array_impl!(30);
-from_array_impl!(31);
-from_array_impl!(32);
-
 impl From<Uuid> for Value {
     fn from(uuid: Uuid) -> Value {
         Value::Bytes(uuid.as_bytes().to_vec())
@@ -1150,10 +1425,18 @@ pub struct UuidIr {
 impl ConvIr<Uuid> for UuidIr {
     fn new(v: Value) -> Result<UuidIr, FromValueError> {
         match v {
-            Value::Bytes(bytes) => match Uuid::from_slice(bytes.as_slice()) {
-                Ok(val) => Ok(UuidIr { val, bytes }),
-                Err(_) => Err(FromValueError(Value::Bytes(bytes))),
-            },
+            Value::Bytes(bytes) => {
+                let parsed = match bytes.len() {
+                    16 => Uuid::from_slice(&bytes).ok(),
+                    // hyphenated or simple textual form
+                    32 | 36 => from_utf8(&bytes).ok().and_then(|x| Uuid::parse_str(x).ok()),
+                    _ => None,
+                };
+                match parsed {
+                    Some(val) => Ok(UuidIr { val, bytes }),
+                    None => Err(FromValueError(Value::Bytes(bytes))),
+                }
+            }
             v => Err(FromValueError(v)),
         }
     }
@@ -1342,6 +1625,270 @@ mod tests {
     unsigned_primitive_roundtrip!(u32, u32_roundtrip);
     unsigned_primitive_roundtrip!(u64, u64_roundtrip);
 
+    #[test]
+    fn should_convert_date_time_with_time_zone_to_utc() {
+        let naive = NaiveDate::from_ymd_opt(2019, 11, 27)
+            .and_then(|date| date.and_hms_micro_opt(12, 30, 0, 123_456))
+            .unwrap();
+        let utc = Utc.from_utc_datetime(&naive);
+        assert_eq!(
+            Value::from(utc),
+            Value::Date(2019, 11, 27, 12, 30, 0, 123_456)
+        );
+
+        let offset = FixedOffset::east_opt(3 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2019, 11, 27, 2, 30, 0)
+            .unwrap();
+        assert_eq!(Value::from(offset), Value::Date(2019, 11, 26, 23, 30, 0, 0));
+    }
+
+    #[test]
+    fn should_convert_bytes_to_array() {
+        let value = Value::Bytes((0..16).collect());
+        let array = from_value::<[u8; 16]>(value.clone());
+        assert_eq!(
+            array,
+            [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]
+        );
+        assert_eq!(Value::from(array), value);
+
+        for value in &[
+            Value::Bytes(vec![0; 15]),
+            Value::Bytes(vec![0; 17]),
+            Value::Int(1),
+        ] {
+            assert_eq!(
+                from_value_opt::<[u8; 16]>(value.clone()).unwrap_err().0,
+                *value
+            );
+        }
+
+        assert_eq!(from_value::<[u8; 0]>(Value::Bytes(Vec::new())), [0_u8; 0]);
+        assert!(from_value_opt::<[u8; 0]>(Value::Bytes(vec![0])).is_err());
+        assert_eq!(Value::from([0xff_u8; 64]), Value::Bytes(vec![0xff; 64]));
+    }
+
+    #[test]
+    fn should_convert_boxed_and_cow_str() {
+        let value = Value::from("foo");
+        assert_eq!(&*from_value::<Box<str>>(value.clone()), "foo");
+        assert_eq!(from_value::<Cow<'static, str>>(value.clone()), "foo");
+        assert_eq!(Value::from(Box::<str>::from("foo")), value);
+        assert_eq!(Value::from(Cow::Borrowed("foo")), value);
+        assert_eq!(Value::from(Cow::<str>::Owned("foo".into())), value);
+
+        for value in &[Value::Bytes(vec![0xff, 0xfe]), Value::Int(1)] {
+            assert_eq!(
+                from_value_opt::<Box<str>>(value.clone()).unwrap_err().0,
+                *value
+            );
+            assert_eq!(
+                from_value_opt::<Cow<'static, str>>(value.clone())
+                    .unwrap_err()
+                    .0,
+                *value
+            );
+        }
+    }
+
+    #[test]
+    fn should_parse_micros() {
+        assert_eq!(parse_micros(b"1"), Some(100_000));
+        assert_eq!(parse_micros(b"01"), Some(10_000));
+        assert_eq!(parse_micros(b"000001"), Some(1));
+        assert_eq!(parse_micros(b"123456"), Some(123_456));
+        assert_eq!(parse_micros(b"1234567"), Some(123_456));
+        assert_eq!(parse_micros(b"0000009"), Some(0));
+
+        assert_eq!(parse_micros(b""), None);
+        assert_eq!(parse_micros(b"12a"), None);
+        assert_eq!(parse_micros(b"1234567a"), None);
+        assert_eq!(parse_micros("\u{663}".as_bytes()), None);
+        assert_eq!(
+            parse_mysql_datetime_string("2019-11-27 12:00:00.\u{663}".as_bytes()),
+            None
+        );
+    }
+
+    #[test]
+    fn should_reject_non_ascii_digits_in_time() {
+        // U+0663 ARABIC-INDIC DIGIT THREE is matched by Unicode-aware `\d`
+        for input in &[
+            "00:0\u{663}:00",
+            "00:00:0\u{663}",
+            "0\u{663}:00:00",
+            "00:00:00.\u{663}",
+        ] {
+            assert_eq!(parse_mysql_time_string(input.as_bytes()), None, "{}", input);
+            let value = Value::from(*input);
+            assert_eq!(
+                from_value_opt::<Duration>(value.clone()).unwrap_err().0,
+                value
+            );
+            assert!(from_value_opt::<NaiveTime>(value).is_err());
+        }
+    }
+
+    #[test]
+    fn should_parse_time_with_many_hour_digits() {
+        assert_eq!(
+            parse_mysql_time_string(b"1000:00:00"),
+            Some((false, 1000, 0, 0, 0))
+        );
+        assert_eq!(
+            parse_mysql_time_string(b"-9999:59:59.999999"),
+            Some((true, 9999, 59, 59, 999_999))
+        );
+        assert_eq!(parse_mysql_time_string(b"1000:60:00"), None);
+        assert_eq!(parse_mysql_time_string(b"1000:00:60"), None);
+        assert_eq!(parse_mysql_time_string(b"0:00:00"), None);
+        assert_eq!(parse_mysql_time_string(b"99999999999:00:00"), None);
+
+        assert_eq!(
+            from_value::<Duration>(Value::from("1000:00:00")),
+            Duration::from_secs(1000 * 3600)
+        );
+        let duration = from_value::<time::Duration>(Value::from("-9999:59:59.999999"));
+        assert_eq!(
+            duration,
+            -(time::Duration::hours(9999)
+                + time::Duration::minutes(59)
+                + time::Duration::seconds(59)
+                + time::Duration::microseconds(999_999))
+        );
+        assert_eq!(
+            Value::from(duration),
+            Value::Time(true, 416, 15, 59, 59, 999_999)
+        );
+    }
+
+    #[test]
+    fn should_reject_negative_std_duration() {
+        for value in &[
+            Value::from("-00:00:01"),
+            Value::from("-000:00:00.000001"),
+            Value::Time(true, 0, 0, 0, 1, 0),
+        ] {
+            let err = Duration::from_value_explained(value.clone()).unwrap_err();
+            assert_eq!(err.value(), value);
+            assert_eq!(err.reason(), Some(NEGATIVE_DURATION));
+            assert!(from_value::<time::Duration>(value.clone()) < time::Duration::zero());
+        }
+        assert_eq!(
+            from_value::<Duration>(Value::from("00:00:01")),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn should_convert_value_to_date_time_as_utc() {
+        let naive = NaiveDate::from_ymd_opt(2019, 11, 27)
+            .and_then(|date| date.and_hms_micro_opt(12, 30, 0, 123_456))
+            .unwrap();
+        let utc = Utc.from_utc_datetime(&naive);
+        let value = Value::Date(2019, 11, 27, 12, 30, 0, 123_456);
+        assert_eq!(from_value::<DateTime<Utc>>(value.clone()), utc);
+        assert_eq!(
+            from_value::<DateTime<Utc>>(Value::from("2019-11-27 12:30:00.123456")),
+            utc
+        );
+        assert_eq!(Value::from(utc), value);
+
+        let fixed = from_value::<DateTime<FixedOffset>>(value.clone());
+        assert_eq!(fixed.offset().local_minus_utc(), 0);
+        assert_eq!(fixed, utc);
+        assert_eq!(Value::from(fixed), value);
+
+        assert!(from_value_opt::<DateTime<Utc>>(Value::from("foo")).is_err());
+        assert!(from_value_opt::<DateTime<Utc>>(Value::Date(0, 0, 0, 0, 0, 0, 0)).is_err());
+    }
+
+    #[test]
+    fn should_resolve_dst_ambiguous_hour_with_assumed_offset() {
+        // 01:30 on 2019-11-03 happens twice in America/New_York: first at EDT (UTC-04:00),
+        // then at EST (UTC-05:00). The assumed offset picks exactly one of the two instants.
+        let value = Value::Date(2019, 11, 3, 1, 30, 0, 0);
+
+        let AssumedOffset(edt) = from_value::<AssumedOffset<{ -4 * 3600 }>>(value.clone());
+        let AssumedOffset(est) = from_value::<AssumedOffset<{ -5 * 3600 }>>(value.clone());
+        assert_eq!(edt, Utc.with_ymd_and_hms(2019, 11, 3, 5, 30, 0).unwrap());
+        assert_eq!(est, Utc.with_ymd_and_hms(2019, 11, 3, 6, 30, 0).unwrap());
+        assert_eq!(est - edt, chrono::Duration::hours(1));
+
+        assert_eq!(Value::from(AssumedOffset::<{ -4 * 3600 }>(edt)), value);
+        assert_eq!(Value::from(AssumedOffset::<{ -5 * 3600 }>(est)), value);
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn should_convert_chrono_tz_date_time() {
+        use chrono_tz::Europe::Paris;
+
+        let date_time = Paris.with_ymd_and_hms(2019, 7, 1, 12, 0, 0).unwrap();
+        let value = Value::from(date_time);
+        assert_eq!(value, Value::Date(2019, 7, 1, 10, 0, 0, 0));
+        assert_eq!(from_value::<NaiveDateTime>(value), date_time.naive_utc());
+    }
+
+    #[test]
+    fn should_name_target_type_in_error() {
+        let err = u32::from_value_explained(Value::from("foo")).unwrap_err();
+        assert_eq!(err.type_name(), "u32");
+        assert_eq!(
+            err.to_string(),
+            "Couldn't convert the value `Bytes(\"foo\")` to `u32`"
+        );
+
+        let err = Option::<u8>::from_value_explained(Value::Int(256)).unwrap_err();
+        assert!(err.type_name().ends_with("Option<u8>"));
+        let err = Option::<NaiveTime>::from_value_explained(Value::from("-01:00:00")).unwrap_err();
+        assert_eq!(err.reason(), Some(NAIVE_TIME_RANGE));
+
+        let err = NaiveTime::from_value_explained(Value::from("25:00:00")).unwrap_err();
+        assert!(err.type_name().ends_with("NaiveTime"));
+        assert!(err
+            .to_string()
+            .ends_with("NaiveTime`: TIME value out of NaiveTime's 0..24h range"));
+
+        let FromValueError(value) = err.into();
+        assert_eq!(value, Value::from("25:00:00"));
+
+        let err = NaiveTime::get_intermediate_explained(value).unwrap_err();
+        assert!(err.type_name().ends_with("NaiveTime"));
+        assert_eq!(err.reason(), Some(NAIVE_TIME_RANGE));
+        let ir = u8::get_intermediate_explained(Value::from("42")).unwrap();
+        assert_eq!(ir.commit(), 42);
+
+        let err = from_value_opt::<u32>(Value::Int(-1)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Couldn't convert the value `Int(-1)` to a desired type"
+        );
+    }
+
+    #[test]
+    fn should_explain_naive_time_range() {
+        for value in &[
+            Value::Time(true, 0, 1, 2, 3, 0),
+            Value::Time(false, 1, 0, 0, 0, 0),
+            Value::from("-01:02:03"),
+            Value::from("25:00:00"),
+        ] {
+            let err = NaiveTime::from_value_explained(value.clone()).unwrap_err();
+            assert_eq!(err.value(), value);
+            assert_eq!(err.reason(), Some(NAIVE_TIME_RANGE));
+            assert!(err.to_string().contains("0..24h range"), "{}", err);
+        }
+
+        let err = NaiveTime::from_value_explained(Value::from("foo")).unwrap_err();
+        assert_eq!(err.reason(), None);
+        assert_eq!(
+            from_value::<NaiveTime>(Value::Time(false, 0, 23, 59, 59, 0)),
+            NaiveTime::from_hms_opt(23, 59, 59).unwrap()
+        );
+    }
+
     #[test]
     fn from_value_should_fail_on_integer_overflow() {
         let value = Value::Bytes(b"340282366920938463463374607431768211456"[..].into());
@@ -1372,6 +1919,49 @@ mod tests {
         assert!(from_value_opt::<i128>(value.clone()).is_err());
     }
 
+    #[test]
+    fn from_value_should_accept_leading_plus_for_unsigned() {
+        assert_eq!(from_value::<u32>(Value::from("+42")), 42);
+        assert_eq!(from_value::<u8>(Value::from("+0")), 0);
+        assert_eq!(
+            from_value::<u64>(Value::from("+18446744073709551615")),
+            u64::MAX
+        );
+        assert_eq!(from_value::<u128>(Value::from("+42")), 42);
+        assert_eq!(from_value::<i32>(Value::from("+42")), 42);
+        for input in &["++1", "+", "-", "-1", "+-1", "+ 1", "1+"] {
+            let value = Value::from(*input);
+            assert_eq!(
+                from_value_opt::<u32>(value.clone()).unwrap_err().0,
+                value,
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn from_value_should_respect_128_bit_boundaries() {
+        let u128_max = Value::Bytes(b"340282366920938463463374607431768211455"[..].into());
+        assert_eq!(from_value::<u128>(u128_max.clone()), u128::MAX);
+        assert!(from_value_opt::<i128>(u128_max).is_err());
+
+        let i128_max = Value::Bytes(b"170141183460469231731687303715884105727"[..].into());
+        assert_eq!(from_value::<i128>(i128_max), i128::MAX);
+        let i128_min = Value::Bytes(b"-170141183460469231731687303715884105728"[..].into());
+        assert_eq!(from_value::<i128>(i128_min.clone()), i128::MIN);
+        assert!(from_value_opt::<u128>(i128_min).is_err());
+
+        let i128_overflow = Value::Bytes(b"170141183460469231731687303715884105728"[..].into());
+        assert!(from_value_opt::<i128>(i128_overflow.clone()).is_err());
+        assert_eq!(from_value::<u128>(i128_overflow), i128::MAX as u128 + 1);
+
+        assert_eq!(from_value::<i128>(Value::Int(i64::MIN)), i64::MIN as i128);
+        assert_eq!(from_value::<i128>(Value::UInt(u64::MAX)), u64::MAX as i128);
+        assert_eq!(from_value::<u128>(Value::UInt(u64::MAX)), u64::MAX as u128);
+        assert!(from_value_opt::<u128>(Value::Int(-1)).is_err());
+    }
+
     #[cfg(feature = "nightly")]
     #[bench]
     fn bench_parse_mysql_datetime_string(bencher: &mut test::Bencher) {
@@ -1391,4 +1981,65 @@ mod tests {
             parse_mysql_time_string(text.as_bytes()).unwrap();
         });
     }
+
+    #[test]
+    fn should_convert_optional_references() {
+        let int = 42_i32;
+        let string = String::from("foo");
+
+        assert_eq!(Value::from(Some(&int)), Value::Int(42));
+        assert_eq!(Value::from(None::<&i32>), Value::NULL);
+        assert_eq!(Value::from(Some("foo")), Value::from("foo"));
+        assert_eq!(Value::from(None::<&str>), Value::NULL);
+        assert_eq!(Value::from(Some(&string)), Value::from("foo"));
+        assert_eq!(Value::from(None::<&String>), Value::NULL);
+    }
+
+    #[test]
+    fn should_expand_collections_into_values() {
+        let expected = vec![Value::Int(1), Value::Int(2), Value::Int(3)];
+
+        let slice: &[i32] = &[1, 2, 3];
+        assert_eq!(slice.into_values(), expected);
+        assert_eq!(vec![1_i32, 2, 3].into_values(), expected);
+        assert_eq!([1_i32, 2, 3].into_values(), expected);
+        assert_eq!(
+            vec!["foo", "bar"].into_values(),
+            vec![Value::from("foo"), Value::from("bar")]
+        );
+        assert_eq!(
+            [Some(1_i32), None].into_values(),
+            vec![Value::Int(1), Value::NULL]
+        );
+
+        let empty: &[i32] = &[];
+        assert!(empty.into_values().is_empty());
+        assert!(Vec::<i32>::new().into_values().is_empty());
+        assert!(<[i32; 0]>::default().into_values().is_empty());
+    }
+
+    #[test]
+    fn should_convert_uuid_encodings() {
+        let uuid = Uuid::parse_str("936da01f-9abd-4d9d-80c7-02af85c822a8").unwrap();
+
+        let binary = Value::Bytes(uuid.as_bytes().to_vec());
+        let hyphenated = Value::from("936DA01F-9ABD-4D9D-80C7-02AF85C822A8");
+        let simple = Value::from("936da01f9abd4d9d80c702af85c822a8");
+        for value in [binary, hyphenated, simple].iter() {
+            assert_eq!(from_value::<Uuid>(value.clone()), uuid);
+        }
+
+        // binary form takes priority over textual
+        let ambiguous = Value::from("0123456789abcdef");
+        assert_eq!(
+            from_value::<Uuid>(ambiguous),
+            Uuid::from_slice(b"0123456789abcdef").unwrap()
+        );
+
+        let malformed = Value::from("936da01f-9abd-4d9d-80c7-02af85c822xx");
+        assert_eq!(
+            from_value_opt::<Uuid>(malformed.clone()).unwrap_err().0,
+            malformed
+        );
+    }
 }
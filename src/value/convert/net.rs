@@ -0,0 +1,93 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+//! This module implements conversion from/to `Value` for IP addresses stored as text.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::from_utf8;
+
+use super::{ConvIr, FromValue, FromValueError, ParseIr, Value};
+
+macro_rules! impl_from_value_ip {
+    ($t:ident) => {
+        impl ConvIr<$t> for ParseIr<$t> {
+            fn new(value: Value) -> Result<ParseIr<$t>, FromValueError> {
+                let output = match value {
+                    Value::Bytes(ref bytes) => from_utf8(bytes).ok().and_then(|s| s.parse().ok()),
+                    _ => None,
+                };
+
+                match output {
+                    Some(output) => Ok(ParseIr { value, output }),
+                    None => Err(FromValueError(value)),
+                }
+            }
+            fn commit(self) -> $t {
+                self.output
+            }
+            fn rollback(self) -> Value {
+                self.value
+            }
+        }
+
+        impl FromValue for $t {
+            type Intermediate = ParseIr<$t>;
+        }
+
+        impl From<$t> for Value {
+            fn from(x: $t) -> Value {
+                Value::Bytes(x.to_string().into_bytes())
+            }
+        }
+    };
+}
+
+impl_from_value_ip!(IpAddr);
+impl_from_value_ip!(Ipv4Addr);
+impl_from_value_ip!(Ipv6Addr);
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    use crate::value::convert::{from_value, from_value_opt};
+    use crate::value::Value;
+
+    #[test]
+    fn should_convert_ip_addr() {
+        let v4 = Ipv4Addr::new(192, 168, 0, 1);
+        assert_eq!(from_value::<Ipv4Addr>(Value::from("192.168.0.1")), v4);
+        assert_eq!(
+            from_value::<IpAddr>(Value::from("192.168.0.1")),
+            IpAddr::V4(v4)
+        );
+        assert_eq!(Value::from(IpAddr::V4(v4)), Value::from("192.168.0.1"));
+
+        let v6 = from_value::<Ipv6Addr>(Value::from("2001:DB8:0:0::1"));
+        assert_eq!(v6, Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+        assert_eq!(Value::from(v6), Value::from("2001:db8::1"));
+
+        let mapped = from_value::<IpAddr>(Value::from("::ffff:192.168.0.1"));
+        assert_eq!(mapped, IpAddr::V6(v4.to_ipv6_mapped()));
+        assert_eq!(Value::from(mapped), Value::from("::ffff:192.168.0.1"));
+    }
+
+    #[test]
+    fn should_reject_invalid_ip_addr() {
+        for input in &["192.168.0.1 ", " ::1", "192.168.0.256", "foo", ""] {
+            let value = Value::from(*input);
+            assert_eq!(
+                from_value_opt::<IpAddr>(value.clone()).unwrap_err().0,
+                value
+            );
+        }
+        assert!(from_value_opt::<Ipv4Addr>(Value::from("::1")).is_err());
+        assert!(from_value_opt::<Ipv6Addr>(Value::from("127.0.0.1")).is_err());
+        assert!(from_value_opt::<IpAddr>(Value::UInt(0x7f00_0001)).is_err());
+    }
+}
@@ -0,0 +1,915 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+//! This module implements lenient conversions from/to `Value` for numeric types.
+
+use lexical::parse;
+use num_traits::FromPrimitive;
+
+use std::convert::TryInto;
+use std::fmt;
+use std::marker::PhantomData;
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use std::str::from_utf8;
+
+use super::{ConvIr, FromValue, FromValueError, ParseIr, Value};
+
+/// Set of characters that `CleanedNumber` strips before parsing.
+pub trait NumberCleaner {
+    /// Returns `true` if `c` should be stripped.
+    fn is_ignored(c: char) -> bool;
+}
+
+/// Default `NumberCleaner` that strips whitespace, `,`, `_`, `'` and common currency symbols.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash, Default)]
+pub struct Currency;
+
+impl NumberCleaner for Currency {
+    fn is_ignored(c: char) -> bool {
+        c.is_whitespace()
+            || matches!(
+                c,
+                ',' | '_' | '\'' | '$' | '€' | '£' | '¥' | '₽' | '₹' | '¢'
+            )
+    }
+}
+
+/// Use it to parse messy numbers such as `"$1,234.50"`.
+///
+/// Characters accepted by `C` are stripped, then the remaining sign, digits and decimal point are
+/// converted to `T`. Leading sign and a single decimal point are kept. Any other character, more
+/// than one decimal point, or nothing left after cleaning, are errors. Non-bytes values are
+/// converted to `T` as is.
+///
+/// ```ignore
+/// let amount = from_value::<CleanedNumber<Decimal>>(Value::from("$1,234.50")).into_inner();
+/// ```
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct CleanedNumber<T, C = Currency>(pub T, PhantomData<C>);
+
+impl<T, C> CleanedNumber<T, C> {
+    /// Wraps `value`.
+    pub fn new(value: T) -> CleanedNumber<T, C> {
+        CleanedNumber(value, PhantomData)
+    }
+
+    /// Unwraps the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// Intermediate result of a Value-to-CleanedNumber conversion.
+#[derive(Debug)]
+pub struct CleanedNumberIr<Ir> {
+    bytes: Option<Vec<u8>>,
+    ir: Ir,
+}
+
+/// Strips characters ignored by `C`. Returns `None` if the rest is not a plain decimal number.
+fn clean_number<C: NumberCleaner>(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut has_digits = false;
+    let mut has_point = false;
+
+    for c in from_utf8(bytes).ok()?.chars() {
+        match c {
+            _ if C::is_ignored(c) => (),
+            '0'..='9' => {
+                has_digits = true;
+                output.push(c as u8);
+            }
+            '.' if !has_point => {
+                has_point = true;
+                output.push(b'.');
+            }
+            '-' | '+' if output.is_empty() => output.push(c as u8),
+            _ => return None,
+        }
+    }
+
+    if has_digits {
+        Some(output)
+    } else {
+        None
+    }
+}
+
+impl<T, C, Ir> ConvIr<CleanedNumber<T, C>> for CleanedNumberIr<Ir>
+where
+    T: FromValue<Intermediate = Ir>,
+    C: NumberCleaner,
+    Ir: ConvIr<T>,
+{
+    fn new(v: Value) -> Result<CleanedNumberIr<Ir>, FromValueError> {
+        match v {
+            Value::Bytes(bytes) => {
+                let ir = clean_number::<C>(&bytes)
+                    .and_then(|cleaned| T::get_intermediate(Value::Bytes(cleaned)).ok());
+                match ir {
+                    Some(ir) => Ok(CleanedNumberIr {
+                        bytes: Some(bytes),
+                        ir,
+                    }),
+                    None => Err(FromValueError(Value::Bytes(bytes))),
+                }
+            }
+            v => T::get_intermediate(v).map(|ir| CleanedNumberIr { bytes: None, ir }),
+        }
+    }
+    fn commit(self) -> CleanedNumber<T, C> {
+        CleanedNumber::new(self.ir.commit())
+    }
+    fn rollback(self) -> Value {
+        match self.bytes {
+            Some(bytes) => Value::Bytes(bytes),
+            None => self.ir.rollback(),
+        }
+    }
+}
+
+impl<T: FromValue, C: NumberCleaner> FromValue for CleanedNumber<T, C> {
+    type Intermediate = CleanedNumberIr<T::Intermediate>;
+}
+
+impl<T: Into<Value>, C> From<CleanedNumber<T, C>> for Value {
+    fn from(x: CleanedNumber<T, C>) -> Value {
+        x.0.into()
+    }
+}
+
+/// Use it to read `u64` stored as exactly eight big-endian bytes (e.g. `BINARY(8)` counter).
+///
+/// `From<BeU64> for Value` emits eight big-endian bytes.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct BeU64(pub u64);
+
+/// Use it to read `u64` stored as exactly eight little-endian bytes.
+///
+/// `From<LeU64> for Value` emits eight little-endian bytes.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct LeU64(pub u64);
+
+macro_rules! impl_binary_u64 {
+    ($t:ident, $from_bytes:ident, $to_bytes:ident) => {
+        impl ConvIr<$t> for ParseIr<$t> {
+            fn new(v: Value) -> Result<ParseIr<$t>, FromValueError> {
+                let output = match v {
+                    Value::Bytes(ref bytes) => match bytes.as_slice().try_into() {
+                        Ok(bytes) => $t(u64::$from_bytes(bytes)),
+                        Err(_) => return Err(FromValueError(v)),
+                    },
+                    v => return Err(FromValueError(v)),
+                };
+                Ok(ParseIr { value: v, output })
+            }
+            fn commit(self) -> $t {
+                self.output
+            }
+            fn rollback(self) -> Value {
+                self.value
+            }
+        }
+
+        impl FromValue for $t {
+            type Intermediate = ParseIr<$t>;
+        }
+
+        impl From<$t> for Value {
+            fn from(x: $t) -> Value {
+                Value::Bytes(x.0.$to_bytes().to_vec())
+            }
+        }
+    };
+}
+
+impl_binary_u64!(BeU64, from_be_bytes, to_be_bytes);
+impl_binary_u64!(LeU64, from_le_bytes, to_le_bytes);
+
+/// Use it to read MySql `BIT(n)` values.
+///
+/// Accepts up to eight bytes holding a big-endian unsigned integer (as `BIT(n)` values are sent)
+/// and also non-negative `Value::Int` and `Value::UInt`. `From<Bits> for Value` emits minimal
+/// big-endian bytes (at least one byte).
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct Bits(pub u64);
+
+impl ConvIr<Bits> for ParseIr<Bits> {
+    fn new(v: Value) -> Result<ParseIr<Bits>, FromValueError> {
+        let output = match v {
+            Value::Bytes(ref bytes) if !bytes.is_empty() && bytes.len() <= 8 => {
+                bytes.iter().fold(0, |acc, x| acc << 8 | u64::from(*x))
+            }
+            Value::Int(x) if x >= 0 => x as u64,
+            Value::UInt(x) => x,
+            v => return Err(FromValueError(v)),
+        };
+        Ok(ParseIr {
+            value: v,
+            output: Bits(output),
+        })
+    }
+    fn commit(self) -> Bits {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl FromValue for Bits {
+    type Intermediate = ParseIr<Bits>;
+}
+
+impl From<Bits> for Value {
+    fn from(x: Bits) -> Value {
+        let bytes = x.0.to_be_bytes();
+        let skip = (x.0.leading_zeros() / 8).min(7) as usize;
+        Value::Bytes(bytes[skip..].to_vec())
+    }
+}
+
+/// Use it to read integers that may be written in scientific notation (e.g. `"1.23E4"`).
+///
+/// Value is converted as `T` first. If it fails, then bytes are parsed as `f64` that must be
+/// integral and within the range of `T`. Note that integers above 2^53 written in scientific
+/// notation could not be represented exactly and will be rounded.
+///
+/// `From<SciInt<T>> for Value` emits plain `T` value.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct SciInt<T>(pub T);
+
+impl<T> ConvIr<SciInt<T>> for ParseIr<SciInt<T>>
+where
+    T: FromValue + FromPrimitive,
+{
+    fn new(v: Value) -> Result<ParseIr<SciInt<T>>, FromValueError> {
+        if let Ok(output) = super::from_value_opt::<T>(v.clone()) {
+            return Ok(ParseIr {
+                value: v,
+                output: SciInt(output),
+            });
+        }
+
+        let output = match v {
+            Value::Bytes(ref bytes) => from_utf8(bytes)
+                .ok()
+                .and_then(|s| s.parse::<f64>().ok())
+                .filter(|x| x.is_finite() && x.fract() == 0.0)
+                .and_then(T::from_f64),
+            _ => None,
+        };
+
+        match output {
+            Some(output) => Ok(ParseIr {
+                value: v,
+                output: SciInt(output),
+            }),
+            None => Err(FromValueError(v)),
+        }
+    }
+    fn commit(self) -> SciInt<T> {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl<T: FromValue + FromPrimitive> FromValue for SciInt<T> {
+    type Intermediate = ParseIr<SciInt<T>>;
+}
+
+impl<T: Into<Value>> From<SciInt<T>> for Value {
+    fn from(x: SciInt<T>) -> Value {
+        x.0.into()
+    }
+}
+
+/// Snowflake ID (64-bit distributed ID).
+///
+/// Value is converted as `u64` (i.e. negative `Value::Int` and overflowing value is rejected).
+/// Layout, from the most significant bit:
+///
+/// * 1 unused bit;
+/// * 41 bits of timestamp (milliseconds since generator-specific epoch);
+/// * 10 bits of worker id;
+/// * 12 bits of sequence number.
+///
+/// `From<Snowflake> for Value` emits `Value::UInt`.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct Snowflake(pub u64);
+
+impl Snowflake {
+    /// Returns timestamp part of this id (milliseconds since generator-specific epoch).
+    pub fn timestamp(self) -> u64 {
+        (self.0 >> 22) & 0x1ff_ffff_ffff
+    }
+
+    /// Returns worker id part of this id.
+    pub fn worker(self) -> u16 {
+        ((self.0 >> 12) & 0x3ff) as u16
+    }
+
+    /// Returns sequence number part of this id.
+    pub fn sequence(self) -> u16 {
+        (self.0 & 0xfff) as u16
+    }
+}
+
+impl ConvIr<Snowflake> for ParseIr<Snowflake> {
+    fn new(v: Value) -> Result<ParseIr<Snowflake>, FromValueError> {
+        let ir = <ParseIr<u64>>::new(v)?;
+        Ok(ParseIr {
+            value: ir.value,
+            output: Snowflake(ir.output),
+        })
+    }
+    fn commit(self) -> Snowflake {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl FromValue for Snowflake {
+    type Intermediate = ParseIr<Snowflake>;
+}
+
+impl From<Snowflake> for Value {
+    fn from(x: Snowflake) -> Value {
+        Value::UInt(x.0)
+    }
+}
+
+/// Fixed-point amount stored as an integer number of minor units (e.g. cents for `SCALE = 2`).
+///
+/// Integer value is read as minor units. Bytes containing `.` are read as a decimal amount
+/// with at most `SCALE` fractional digits (i.e. the string produced by `Display`), other bytes
+/// are read as minor units, so `"12"` is 12 minor units while `"12.0"` is twelve whole units.
+/// `From<MinorUnits<SCALE>> for Value` emits the decimal amount as a string (`"-12.345"` for
+/// `MinorUnits::<3>(-12345)`, `"42"` for `MinorUnits::<0>(42)`).
+///
+/// `i64` has at most 18 meaningful fractional digits, so `SCALE` above 18 fails to compile:
+///
+/// ```compile_fail
+/// use mysql_common::value::{convert::{from_value, MinorUnits}, Value};
+///
+/// from_value::<MinorUnits<19>>(Value::Int(1));
+/// ```
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct MinorUnits<const SCALE: u32>(pub i64);
+
+impl<const SCALE: u32> MinorUnits<SCALE> {
+    /// Fails the build for `SCALE` above 18, i.e. more fractional digits than `i64` can hold.
+    const SCALE_CHECK: () = assert!(SCALE <= 18, "MinorUnits supports SCALE up to 18");
+
+    /// Parses decimal amount (e.g. `"-12.345"`) with at most `SCALE` fractional digits.
+    fn parse_amount(bytes: &[u8]) -> Option<MinorUnits<SCALE>> {
+        let () = Self::SCALE_CHECK;
+        let s = from_utf8(bytes).ok()?;
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let (major, minor) = s.split_once('.')?;
+        let is_digits = |x: &str| x.bytes().all(|b| b.is_ascii_digit());
+        if major.is_empty()
+            || minor.is_empty()
+            || minor.len() > SCALE as usize
+            || !is_digits(major)
+            || !is_digits(minor)
+        {
+            return None;
+        }
+        let scale = 10_i128.pow(SCALE - minor.len() as u32);
+        let minor_units = major
+            .parse::<i128>()
+            .ok()?
+            .checked_mul(10_i128.pow(SCALE))?
+            .checked_add(minor.parse::<i128>().ok()? * scale)?;
+        let minor_units = if negative { -minor_units } else { minor_units };
+        minor_units.try_into().ok().map(MinorUnits)
+    }
+}
+
+impl<const SCALE: u32> fmt::Display for MinorUnits<SCALE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let () = Self::SCALE_CHECK;
+        let unit = 10_u128.pow(SCALE);
+        let abs = i128::from(self.0).unsigned_abs();
+        let sign = if self.0 < 0 { "-" } else { "" };
+        if SCALE == 0 {
+            write!(f, "{}{}", sign, abs)
+        } else {
+            write!(
+                f,
+                "{}{}.{:0width$}",
+                sign,
+                abs / unit,
+                abs % unit,
+                width = SCALE as usize
+            )
+        }
+    }
+}
+
+impl<const SCALE: u32> ConvIr<MinorUnits<SCALE>> for ParseIr<MinorUnits<SCALE>> {
+    fn new(v: Value) -> Result<ParseIr<MinorUnits<SCALE>>, FromValueError> {
+        let () = MinorUnits::<SCALE>::SCALE_CHECK;
+        if let Value::Bytes(ref bytes) = v {
+            if bytes.contains(&b'.') {
+                return match MinorUnits::parse_amount(bytes) {
+                    Some(output) => Ok(ParseIr { value: v, output }),
+                    None => Err(FromValueError(v)),
+                };
+            }
+        }
+        let ir = <ParseIr<i64>>::new(v)?;
+        Ok(ParseIr {
+            value: ir.value,
+            output: MinorUnits(ir.output),
+        })
+    }
+    fn commit(self) -> MinorUnits<SCALE> {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl<const SCALE: u32> FromValue for MinorUnits<SCALE> {
+    type Intermediate = ParseIr<MinorUnits<SCALE>>;
+}
+
+impl<const SCALE: u32> From<MinorUnits<SCALE>> for Value {
+    fn from(x: MinorUnits<SCALE>) -> Value {
+        Value::Bytes(x.to_string().into_bytes())
+    }
+}
+
+/// Integer within inclusive range `[MIN, MAX]`.
+///
+/// Value is converted as `i64` first (so malformed or overflowing value is rejected as usual)
+/// and then checked against the bounds.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct Bounded<const MIN: i64, const MAX: i64>(i64);
+
+impl<const MIN: i64, const MAX: i64> Bounded<MIN, MAX> {
+    /// Returns `None` if `x` is outside of `[MIN, MAX]`.
+    pub fn new(x: i64) -> Option<Bounded<MIN, MAX>> {
+        if MIN <= x && x <= MAX {
+            Some(Bounded(x))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the wrapped integer.
+    pub fn get(self) -> i64 {
+        self.0
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> ConvIr<Bounded<MIN, MAX>> for ParseIr<Bounded<MIN, MAX>> {
+    fn new(v: Value) -> Result<ParseIr<Bounded<MIN, MAX>>, FromValueError> {
+        let ir = <ParseIr<i64>>::new(v)?;
+        match Bounded::new(ir.output) {
+            Some(output) => Ok(ParseIr {
+                value: ir.value,
+                output,
+            }),
+            None => Err(FromValueError(ir.value)),
+        }
+    }
+    fn commit(self) -> Bounded<MIN, MAX> {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> FromValue for Bounded<MIN, MAX> {
+    type Intermediate = ParseIr<Bounded<MIN, MAX>>;
+}
+
+impl<const MIN: i64, const MAX: i64> From<Bounded<MIN, MAX>> for Value {
+    fn from(x: Bounded<MIN, MAX>) -> Value {
+        Value::Int(x.0)
+    }
+}
+
+/// Use it to accept non-finite floats (e.g. `"inf"`, `"-inf"` or `"nan"` bytes).
+///
+/// Plain `f32` and `f64` conversions reject bytes parsed to a non-finite value.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub struct AllowNonFinite<T>(pub T);
+
+macro_rules! impl_allow_non_finite {
+    ($t:ident) => {
+        impl ConvIr<AllowNonFinite<$t>> for ParseIr<AllowNonFinite<$t>> {
+            fn new(v: Value) -> Result<ParseIr<AllowNonFinite<$t>>, FromValueError> {
+                let output = match v {
+                    Value::Bytes(ref bytes) => match parse::<$t, _>(bytes) {
+                        Ok(output) => output,
+                        Err(_) => return Err(FromValueError(v)),
+                    },
+                    Value::Float(x) if !x.is_finite() => x as $t,
+                    v => {
+                        let ParseIr { value, output } = ParseIr::<$t>::new(v)?;
+                        return Ok(ParseIr {
+                            value,
+                            output: AllowNonFinite(output),
+                        });
+                    }
+                };
+                Ok(ParseIr {
+                    value: v,
+                    output: AllowNonFinite(output),
+                })
+            }
+            fn commit(self) -> AllowNonFinite<$t> {
+                self.output
+            }
+            fn rollback(self) -> Value {
+                self.value
+            }
+        }
+
+        impl FromValue for AllowNonFinite<$t> {
+            type Intermediate = ParseIr<AllowNonFinite<$t>>;
+        }
+
+        impl From<AllowNonFinite<$t>> for Value {
+            fn from(x: AllowNonFinite<$t>) -> Value {
+                x.0.into()
+            }
+        }
+    };
+}
+
+impl_allow_non_finite!(f32);
+impl_allow_non_finite!(f64);
+
+/// Reason of rejecting zero as `NonZero*` integer.
+const ZERO: &str = "zero is not allowed";
+
+/// `NonZero*` integers are converted as the corresponding primitive, zero is rejected.
+macro_rules! impl_non_zero {
+    ($t:ident, $base:ident) => {
+        impl ConvIr<$t> for ParseIr<$t> {
+            fn new(v: Value) -> Result<ParseIr<$t>, FromValueError> {
+                let ParseIr { value, output } = ParseIr::<$base>::new(v)?;
+                match $t::new(output) {
+                    Some(output) => Ok(ParseIr { value, output }),
+                    None => Err(FromValueError(value)),
+                }
+            }
+            fn commit(self) -> $t {
+                self.output
+            }
+            fn rollback(self) -> Value {
+                self.value
+            }
+            fn explain(value: &Value) -> Option<&'static str> {
+                match ParseIr::<$base>::new(value.clone()) {
+                    Ok(ParseIr { output: 0, .. }) => Some(ZERO),
+                    _ => None,
+                }
+            }
+        }
+
+        impl FromValue for $t {
+            type Intermediate = ParseIr<$t>;
+        }
+
+        impl From<$t> for Value {
+            fn from(x: $t) -> Value {
+                x.get().into()
+            }
+        }
+    };
+}
+
+impl_non_zero!(NonZeroI8, i8);
+impl_non_zero!(NonZeroU8, u8);
+impl_non_zero!(NonZeroI16, i16);
+impl_non_zero!(NonZeroU16, u16);
+impl_non_zero!(NonZeroI32, i32);
+impl_non_zero!(NonZeroU32, u32);
+impl_non_zero!(NonZeroI64, i64);
+impl_non_zero!(NonZeroU64, u64);
+impl_non_zero!(NonZeroIsize, isize);
+impl_non_zero!(NonZeroUsize, usize);
+impl_non_zero!(NonZeroI128, i128);
+impl_non_zero!(NonZeroU128, u128);
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use std::num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64, NonZeroU8};
+    use std::str::FromStr;
+
+    use super::super::*;
+    use super::{NumberCleaner, ZERO};
+
+    #[test]
+    fn should_parse_cleaned_number() {
+        let value = Value::from("$1,234.50");
+        let amount = from_value::<CleanedNumber<Decimal>>(value.clone()).into_inner();
+        assert_eq!(amount, Decimal::from_str("1234.50").unwrap());
+        assert_eq!(from_value::<CleanedNumber<f64>>(value).0, 1234.5);
+
+        assert_eq!(
+            from_value::<CleanedNumber<i64>>(Value::from("-1 000 000")).0,
+            -1_000_000
+        );
+        assert_eq!(from_value::<CleanedNumber<i64>>(Value::from("€ 42")).0, 42);
+        assert_eq!(from_value::<CleanedNumber<i64>>(Value::Int(7)).0, 7);
+    }
+
+    #[test]
+    fn should_reject_malformed_cleaned_number() {
+        for input in &["1.234.50", "$", "", "12a", "1-2", "--1", "1,234.5"] {
+            let value = Value::from(*input);
+            let err = from_value_opt::<CleanedNumber<i64>>(value.clone()).unwrap_err();
+            assert_eq!(err.0, value, "{}", input);
+        }
+        assert!(from_value_opt::<CleanedNumber<f64>>(Value::from("1.2.3")).is_err());
+    }
+
+    #[test]
+    fn should_use_custom_cleaner() {
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        struct Spaces;
+
+        impl NumberCleaner for Spaces {
+            fn is_ignored(c: char) -> bool {
+                c == ' '
+            }
+        }
+
+        let number = from_value::<CleanedNumber<u32, Spaces>>(Value::from("1 234"));
+        assert_eq!(number.into_inner(), 1234);
+        assert!(from_value_opt::<CleanedNumber<u32, Spaces>>(Value::from("1,234")).is_err());
+    }
+
+    #[test]
+    fn should_read_binary_u64() {
+        let value = Value::Bytes(vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]);
+        assert_eq!(
+            from_value::<BeU64>(value.clone()),
+            BeU64(0x0102_0304_0506_0708)
+        );
+        assert_eq!(
+            from_value::<LeU64>(value.clone()),
+            LeU64(0x0807_0605_0403_0201)
+        );
+        assert_eq!(Value::from(BeU64(0x0102_0304_0506_0708)), value);
+        assert_eq!(Value::from(LeU64(0x0807_0605_0403_0201)), value);
+    }
+
+    #[test]
+    fn should_reject_binary_u64_of_wrong_length() {
+        for bytes in &[vec![], vec![0; 7], vec![0; 9]] {
+            let value = Value::Bytes(bytes.clone());
+            assert_eq!(from_value_opt::<BeU64>(value.clone()).unwrap_err().0, value);
+            assert_eq!(from_value_opt::<LeU64>(value.clone()).unwrap_err().0, value);
+        }
+        assert!(from_value_opt::<BeU64>(Value::UInt(1)).is_err());
+    }
+
+    #[test]
+    fn should_parse_scientific_integer() {
+        assert_eq!(from_value::<SciInt<u32>>(Value::from("1.23E4")).0, 12300);
+        assert_eq!(from_value::<SciInt<i64>>(Value::from("-5e2")).0, -500);
+        assert_eq!(from_value::<SciInt<i64>>(Value::from("42")).0, 42);
+        assert_eq!(from_value::<SciInt<i64>>(Value::from("1.235E4")).0, 12350);
+        assert_eq!(from_value::<SciInt<u64>>(Value::UInt(u64::MAX)).0, u64::MAX);
+        assert_eq!(
+            from_value::<SciInt<u64>>(Value::from("18446744073709551615")).0,
+            u64::MAX
+        );
+        assert_eq!(Value::from(SciInt(12300_u32)), Value::UInt(12300));
+
+        for value in &[
+            Value::from("1.23456E4"),
+            Value::from("0.5"),
+            Value::from("1E10"),
+            Value::from("-1E2"),
+            Value::from("inf"),
+            Value::from("NaN"),
+            Value::from("foo"),
+            Value::Float(12300.0),
+        ] {
+            assert_eq!(
+                from_value_opt::<SciInt<u32>>(value.clone()).unwrap_err().0,
+                *value
+            );
+        }
+        assert!(from_value_opt::<u32>(Value::from("1.23E4")).is_err());
+    }
+
+    #[test]
+    fn should_decompose_snowflake() {
+        let id = (1_288_834_974_657 << 22) | (42 << 12) | 7;
+        for value in &[
+            Value::UInt(id),
+            Value::Int(id as i64),
+            Value::from(id.to_string()),
+        ] {
+            let snowflake = from_value::<Snowflake>(value.clone());
+            assert_eq!(snowflake, Snowflake(id));
+            assert_eq!(snowflake.timestamp(), 1_288_834_974_657);
+            assert_eq!(snowflake.worker(), 42);
+            assert_eq!(snowflake.sequence(), 7);
+        }
+        assert_eq!(Value::from(Snowflake(id)), Value::UInt(id));
+
+        for value in &[
+            Value::Int(-1),
+            Value::from("-1"),
+            Value::from("18446744073709551616"),
+            Value::from("foo"),
+        ] {
+            assert_eq!(
+                from_value_opt::<Snowflake>(value.clone()).unwrap_err().0,
+                *value
+            );
+        }
+    }
+
+    #[test]
+    fn should_convert_minor_units() {
+        let amount = from_value::<MinorUnits<3>>(Value::Int(12345));
+        assert_eq!(amount, MinorUnits(12345));
+        assert_eq!(amount.to_string(), "12.345");
+        assert_eq!(Value::from(amount), Value::from("12.345"));
+        assert_eq!(from_value::<MinorUnits<3>>(Value::from("12.345")), amount);
+        assert_eq!(from_value::<MinorUnits<3>>(Value::from("12.3")).0, 12300);
+        assert_eq!(from_value::<MinorUnits<3>>(Value::from("12345")), amount);
+        assert_eq!(from_value::<MinorUnits<3>>(Value::from("12")).0, 12);
+        assert_eq!(from_value::<MinorUnits<3>>(Value::from("12.0")).0, 12000);
+
+        assert_eq!(MinorUnits::<3>(-5).to_string(), "-0.005");
+        assert_eq!(MinorUnits::<3>(-12345).to_string(), "-12.345");
+        assert_eq!(from_value::<MinorUnits<3>>(Value::from("-0.005")).0, -5);
+        assert_eq!(
+            MinorUnits::<3>(i64::MIN).to_string(),
+            "-9223372036854775.808"
+        );
+
+        let max_scale = from_value::<MinorUnits<18>>(Value::from("-9.223372036854775808"));
+        assert_eq!(max_scale, MinorUnits(i64::MIN));
+        assert_eq!(max_scale.to_string(), "-9.223372036854775808");
+
+        let yen = from_value::<MinorUnits<0>>(Value::UInt(500));
+        assert_eq!(yen.to_string(), "500");
+        assert_eq!(MinorUnits::<0>(-500).to_string(), "-500");
+        assert_eq!(Value::from(yen), Value::from("500"));
+
+        for value in &[
+            Value::from("1.2345"),
+            Value::from("1."),
+            Value::from(".5"),
+            Value::from("1.-5"),
+            Value::from("1.2.3"),
+            Value::from("foo"),
+            Value::from("99999999999999999.999"),
+            Value::Float(1.5),
+        ] {
+            assert_eq!(
+                from_value_opt::<MinorUnits<3>>(value.clone())
+                    .unwrap_err()
+                    .0,
+                *value
+            );
+        }
+        assert!(from_value_opt::<MinorUnits<0>>(Value::from("1.5")).is_err());
+    }
+
+    #[test]
+    fn should_check_bounds() {
+        type Percent = Bounded<1, 100>;
+
+        assert_eq!(from_value::<Percent>(Value::Int(1)).get(), 1);
+        assert_eq!(from_value::<Percent>(Value::UInt(100)).get(), 100);
+        assert_eq!(from_value::<Percent>(Value::from("42")).get(), 42);
+        for value in &[
+            Value::Int(0),
+            Value::Int(101),
+            Value::Int(-5),
+            Value::UInt(u64::MAX),
+            Value::from("1000"),
+            Value::from("99999999999999999999"),
+        ] {
+            assert_eq!(
+                from_value_opt::<Percent>(value.clone()).unwrap_err().0,
+                *value
+            );
+        }
+        assert_eq!(Value::from(Percent::new(7).unwrap()), Value::Int(7));
+        assert_eq!(Percent::new(0), None);
+    }
+
+    #[test]
+    fn strict_conversion_should_reject_messy_number() {
+        assert!(from_value_opt::<f64>(Value::from("$1,234.50")).is_err());
+    }
+
+    #[test]
+    fn should_convert_non_zero_integers() {
+        let id = from_value::<NonZeroU64>(Value::UInt(42));
+        assert_eq!(id.get(), 42);
+        assert_eq!(Value::from(id), Value::UInt(42));
+        assert_eq!(from_value::<NonZeroU64>(Value::from("42")), id);
+        assert_eq!(from_value::<NonZeroI32>(Value::Int(-1)).get(), -1);
+        assert_eq!(Value::from(NonZeroI64::new(-1).unwrap()), Value::Int(-1));
+    }
+
+    #[test]
+    fn should_reject_zero_non_zero_integers() {
+        for value in &[Value::Int(0), Value::UInt(0), Value::from("0")] {
+            let err = NonZeroU32::from_value_explained(value.clone()).unwrap_err();
+            assert_eq!(err.value(), value);
+            assert_eq!(err.reason(), Some(ZERO));
+            assert!(from_value_opt::<NonZeroI64>(value.clone()).is_err());
+        }
+        assert!(from_value_opt::<NonZeroU8>(Value::Int(256)).is_err());
+        assert!(from_value_opt::<NonZeroU8>(Value::Int(-1)).is_err());
+    }
+
+    #[test]
+    fn should_reject_non_finite_floats() {
+        for input in &["nan", "NaN", "inf", "-inf", "Infinity"] {
+            let value = Value::from(*input);
+            let err = f64::from_value_explained(value.clone()).unwrap_err();
+            assert_eq!(*err.value(), value);
+            assert_eq!(err.reason(), Some("non-finite float"));
+            assert!(from_value_opt::<f32>(value).is_err());
+        }
+        // out of f32 range
+        assert!(from_value_opt::<f32>(Value::from("1e39")).is_err());
+        assert_eq!(from_value::<f64>(Value::from("1.5")), 1.5);
+    }
+
+    #[test]
+    fn should_allow_non_finite_floats() {
+        let AllowNonFinite(x) = from_value::<AllowNonFinite<f64>>(Value::from("nan"));
+        assert!(x.is_nan());
+        let AllowNonFinite(x) = from_value::<AllowNonFinite<f64>>(Value::from("-inf"));
+        assert_eq!(x, f64::NEG_INFINITY);
+        let AllowNonFinite(x) = from_value::<AllowNonFinite<f32>>(Value::from("inf"));
+        assert_eq!(x, f32::INFINITY);
+        let AllowNonFinite(x) = from_value::<AllowNonFinite<f32>>(Value::Float(f64::NAN));
+        assert!(x.is_nan());
+        let AllowNonFinite(x) = from_value::<AllowNonFinite<f32>>(Value::Float(1.5));
+        assert_eq!(x, 1.5);
+        assert!(from_value_opt::<AllowNonFinite<f64>>(Value::from("foo")).is_err());
+        assert!(from_value_opt::<AllowNonFinite<f64>>(Value::NULL).is_err());
+    }
+
+    #[test]
+    fn should_convert_bits() {
+        // BIT(1)
+        assert_eq!(from_value::<Bits>(Value::Bytes(b"\x01".to_vec())), Bits(1));
+        // BIT(16)
+        assert_eq!(
+            from_value::<Bits>(Value::Bytes(b"\x01\x00".to_vec())),
+            Bits(256)
+        );
+        // BIT(64)
+        assert_eq!(
+            from_value::<Bits>(Value::Bytes(vec![0xff; 8])),
+            Bits(u64::MAX)
+        );
+        assert_eq!(from_value::<Bits>(Value::Int(5)), Bits(5));
+        assert_eq!(from_value::<Bits>(Value::UInt(5)), Bits(5));
+
+        for value in &[
+            Value::Bytes(Vec::new()),
+            Value::Bytes(vec![1; 9]),
+            Value::Int(-1),
+            Value::NULL,
+        ] {
+            assert_eq!(from_value_opt::<Bits>(value.clone()).unwrap_err().0, *value);
+        }
+
+        assert_eq!(Value::from(Bits(0)), Value::Bytes(vec![0]));
+        assert_eq!(Value::from(Bits(1)), Value::Bytes(vec![1]));
+        assert_eq!(Value::from(Bits(256)), Value::Bytes(vec![1, 0]));
+        assert_eq!(Value::from(Bits(u64::MAX)), Value::Bytes(vec![0xff; 8]));
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+use super::{from_value_opt, FromValue, FromValueError, Value};
+
+/// Three-state result of a conversion of a value that may be absent or `NULL`.
+///
+/// See `presence` and `Row::presence`.
+#[derive(Debug)]
+pub enum Presence<T> {
+    /// There is no value (e.g. column doesn't exist or was taken).
+    Absent,
+    /// Value is `NULL`.
+    Null,
+    /// Value was converted to `T`.
+    Present(T),
+    /// Value is not `NULL` but couldn't be converted to `T`.
+    Invalid(FromValueError),
+}
+
+impl<T> Presence<T> {
+    /// Returns `true` if this is `Presence::Present`.
+    pub fn is_present(&self) -> bool {
+        matches!(self, Presence::Present(_))
+    }
+
+    /// Converts to `Option<T>` discarding the reason of the value absence.
+    pub fn present(self) -> Option<T> {
+        match self {
+            Presence::Present(x) => Some(x),
+            _ => None,
+        }
+    }
+}
+
+/// Will convert `v` to `Presence<T>`, where `None` means that the value is absent.
+pub fn presence<T: FromValue>(v: Option<Value>) -> Presence<T> {
+    match v {
+        None => Presence::Absent,
+        Some(Value::NULL) => Presence::Null,
+        Some(v) => match from_value_opt(v) {
+            Ok(x) => Presence::Present(x),
+            Err(err) => Presence::Invalid(err),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{presence, Presence};
+    use crate::value::Value;
+
+    #[test]
+    fn should_convert_to_presence() {
+        assert!(matches!(presence::<i32>(None), Presence::Absent));
+        assert!(matches!(presence::<i32>(Some(Value::NULL)), Presence::Null));
+        assert!(matches!(
+            presence::<i32>(Some(Value::Int(42))),
+            Presence::Present(42)
+        ));
+        match presence::<i32>(Some(Value::from("foo"))) {
+            Presence::Invalid(err) => assert_eq!(err.0, Value::from("foo")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        assert_eq!(presence::<i32>(Some(Value::Int(1))).present(), Some(1));
+        assert!(!presence::<i32>(Some(Value::NULL)).is_present());
+    }
+}
@@ -0,0 +1,522 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+//! This module implements conversions from/to `Value` for validated string types.
+
+use uuid::Uuid;
+
+use std::str::from_utf8;
+
+use super::{ConvIr, FromValue, FromValueError, ParseIr, Value};
+
+/// String that contains at least one non-whitespace character.
+///
+/// Whitespace-only strings are considered empty and are rejected.
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct NonEmptyString(String);
+
+impl NonEmptyString {
+    /// Returns `None` if `s` is empty or consists of whitespace only.
+    pub fn new<T: Into<String>>(s: T) -> Option<NonEmptyString> {
+        let s = s.into();
+        if s.trim().is_empty() {
+            None
+        } else {
+            Some(NonEmptyString(s))
+        }
+    }
+
+    /// Returns the wrapped string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Unwraps the wrapped string.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+/// Intermediate result of a Value-to-NonEmptyString conversion.
+#[derive(Debug)]
+pub struct NonEmptyStringIr {
+    bytes: Vec<u8>,
+}
+
+impl ConvIr<NonEmptyString> for NonEmptyStringIr {
+    fn new(v: Value) -> Result<NonEmptyStringIr, FromValueError> {
+        match v {
+            Value::Bytes(bytes) => match from_utf8(&bytes) {
+                Ok(s) if !s.trim().is_empty() => Ok(NonEmptyStringIr { bytes }),
+                _ => Err(FromValueError(Value::Bytes(bytes))),
+            },
+            v => Err(FromValueError(v)),
+        }
+    }
+    fn commit(self) -> NonEmptyString {
+        NonEmptyString(unsafe { String::from_utf8_unchecked(self.bytes) })
+    }
+    fn rollback(self) -> Value {
+        Value::Bytes(self.bytes)
+    }
+}
+
+impl FromValue for NonEmptyString {
+    type Intermediate = NonEmptyStringIr;
+}
+
+impl From<NonEmptyString> for Value {
+    fn from(x: NonEmptyString) -> Value {
+        Value::Bytes(x.0.into_bytes())
+    }
+}
+
+/// Use it to read NUL-terminated (or NUL-padded) strings.
+///
+/// Bytes are truncated at the first NUL byte before UTF-8 validation, so `b"abc\0\0"` becomes
+/// `"abc"`. If there is no NUL byte, then the whole value is used. Plain `String` conversion
+/// keeps all the bytes.
+///
+/// `From<CStrBytes> for Value` emits the string bytes without a terminator.
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct CStrBytes(pub String);
+
+/// Intermediate result of a Value-to-CStrBytes conversion.
+#[derive(Debug)]
+pub struct CStrBytesIr {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl ConvIr<CStrBytes> for CStrBytesIr {
+    fn new(v: Value) -> Result<CStrBytesIr, FromValueError> {
+        match v {
+            Value::Bytes(bytes) => {
+                let len = bytes.iter().position(|x| *x == 0).unwrap_or(bytes.len());
+                match from_utf8(&bytes[..len]) {
+                    Ok(_) => Ok(CStrBytesIr { bytes, len }),
+                    Err(_) => Err(FromValueError(Value::Bytes(bytes))),
+                }
+            }
+            v => Err(FromValueError(v)),
+        }
+    }
+    fn commit(mut self) -> CStrBytes {
+        self.bytes.truncate(self.len);
+        CStrBytes(unsafe { String::from_utf8_unchecked(self.bytes) })
+    }
+    fn rollback(self) -> Value {
+        Value::Bytes(self.bytes)
+    }
+}
+
+impl FromValue for CStrBytes {
+    type Intermediate = CStrBytesIr;
+}
+
+impl From<CStrBytes> for Value {
+    fn from(x: CStrBytes) -> Value {
+        Value::Bytes(x.0.into_bytes())
+    }
+}
+
+/// Use it to read a single CSV field (as per RFC 4180).
+///
+/// Field surrounded by double quotes is unquoted and doubled quotes inside it are unescaped,
+/// so `"\"hello, \"\"world\"\"\""` becomes `hello, "world"`. Unquoted field is kept as is, but
+/// it must not contain double quotes. Unbalanced quotes are rejected. Plain `String` conversion
+/// keeps the quotes.
+///
+/// `From<CsvField> for Value` emits the field quoted if it contains `,`, `"` or a line break.
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct CsvField(pub String);
+
+/// Intermediate result of a Value-to-CsvField conversion.
+#[derive(Debug)]
+pub struct CsvFieldIr {
+    bytes: Vec<u8>,
+    output: String,
+}
+
+fn parse_csv_field(field: &str) -> Option<String> {
+    let inner = match field.strip_prefix('"') {
+        Some(rest) => rest.strip_suffix('"')?,
+        None if field.contains('"') => return None,
+        None => return Some(field.into()),
+    };
+
+    let mut output = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == '"' && chars.next() != Some('"') {
+            return None;
+        }
+        output.push(c);
+    }
+    Some(output)
+}
+
+impl ConvIr<CsvField> for CsvFieldIr {
+    fn new(v: Value) -> Result<CsvFieldIr, FromValueError> {
+        match v {
+            Value::Bytes(bytes) => match from_utf8(&bytes).ok().and_then(parse_csv_field) {
+                Some(output) => Ok(CsvFieldIr { bytes, output }),
+                None => Err(FromValueError(Value::Bytes(bytes))),
+            },
+            v => Err(FromValueError(v)),
+        }
+    }
+    fn commit(self) -> CsvField {
+        CsvField(self.output)
+    }
+    fn rollback(self) -> Value {
+        Value::Bytes(self.bytes)
+    }
+}
+
+impl FromValue for CsvField {
+    type Intermediate = CsvFieldIr;
+}
+
+impl From<CsvField> for Value {
+    fn from(CsvField(x): CsvField) -> Value {
+        if x.contains(&[',', '"', '\r', '\n'][..]) {
+            Value::Bytes(format!("\"{}\"", x.replace('"', "\"\"")).into_bytes())
+        } else {
+            Value::Bytes(x.into_bytes())
+        }
+    }
+}
+
+impl ConvIr<char> for ParseIr<char> {
+    fn new(value: Value) -> Result<ParseIr<char>, FromValueError> {
+        let output = match value {
+            Value::Bytes(ref bytes) => from_utf8(bytes).ok().and_then(|s| {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Some(c),
+                    _ => None,
+                }
+            }),
+            _ => None,
+        };
+
+        match output {
+            Some(output) => Ok(ParseIr { value, output }),
+            None => Err(FromValueError(value)),
+        }
+    }
+    fn commit(self) -> char {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl FromValue for char {
+    type Intermediate = ParseIr<char>;
+}
+
+impl From<char> for Value {
+    fn from(x: char) -> Value {
+        Value::Bytes(x.to_string().into_bytes())
+    }
+}
+
+/// Use it to read `Uuid` stored as a hyphenated string
+/// (e.g. `"67e55044-10b1-426f-9247-bb680e5fe0c8"`).
+///
+/// Unlike plain `Uuid` conversion, that prefers 16 raw bytes and also accepts the simple form,
+/// this accepts only the hyphenated form. Other textual forms (simple, braced, urn) are
+/// rejected. Digits are case-insensitive. `From<UuidStr> for Value` emits lowercase
+/// hyphenated string.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct UuidStr(pub Uuid);
+
+impl ConvIr<UuidStr> for ParseIr<UuidStr> {
+    fn new(value: Value) -> Result<ParseIr<UuidStr>, FromValueError> {
+        let output = match value {
+            Value::Bytes(ref bytes) if bytes.len() == 36 => {
+                from_utf8(bytes).ok().and_then(|s| Uuid::parse_str(s).ok())
+            }
+            _ => None,
+        };
+
+        match output {
+            Some(output) => Ok(ParseIr {
+                value,
+                output: UuidStr(output),
+            }),
+            None => Err(FromValueError(value)),
+        }
+    }
+    fn commit(self) -> UuidStr {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl FromValue for UuidStr {
+    type Intermediate = ParseIr<UuidStr>;
+}
+
+impl From<UuidStr> for Value {
+    fn from(x: UuidStr) -> Value {
+        Value::Bytes(x.0.to_hyphenated().to_string().into_bytes())
+    }
+}
+
+/// Use it to read a list stored as `DELIMITER`-delimited text (e.g. `"a,b,c"`).
+///
+/// Bytes must be valid utf8. Empty value gives an empty list (not `[""]`). Empty elements
+/// (including the one after a trailing delimiter, so `"a,b,"` is `["a", "b", ""]`) are kept,
+/// unless `SKIP_EMPTY` is `true`. Elements are not trimmed. `From<DelimitedList> for Value` joins
+/// elements with `DELIMITER` (elements containing the delimiter won't round-trip).
+///
+/// ```ignore
+/// let DelimitedList(tags) = from_value::<DelimitedList>(Value::from("a,b,c"));
+/// let DelimitedList(lines) = from_value::<DelimitedList<'\n', true>>(value);
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct DelimitedList<const DELIMITER: char = ',', const SKIP_EMPTY: bool = false>(
+    pub Vec<String>,
+);
+
+impl<const DELIMITER: char, const SKIP_EMPTY: bool> ConvIr<DelimitedList<DELIMITER, SKIP_EMPTY>>
+    for ParseIr<DelimitedList<DELIMITER, SKIP_EMPTY>>
+{
+    fn new(value: Value) -> Result<ParseIr<DelimitedList<DELIMITER, SKIP_EMPTY>>, FromValueError> {
+        let output = match value {
+            Value::Bytes(ref bytes) => from_utf8(bytes).ok().map(|s| {
+                if s.is_empty() {
+                    Vec::new()
+                } else {
+                    s.split(DELIMITER)
+                        .filter(|x| !(SKIP_EMPTY && x.is_empty()))
+                        .map(String::from)
+                        .collect()
+                }
+            }),
+            _ => None,
+        };
+
+        match output {
+            Some(output) => Ok(ParseIr {
+                value,
+                output: DelimitedList(output),
+            }),
+            None => Err(FromValueError(value)),
+        }
+    }
+    fn commit(self) -> DelimitedList<DELIMITER, SKIP_EMPTY> {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl<const DELIMITER: char, const SKIP_EMPTY: bool> FromValue
+    for DelimitedList<DELIMITER, SKIP_EMPTY>
+{
+    type Intermediate = ParseIr<DelimitedList<DELIMITER, SKIP_EMPTY>>;
+}
+
+impl<const DELIMITER: char, const SKIP_EMPTY: bool> From<DelimitedList<DELIMITER, SKIP_EMPTY>>
+    for Value
+{
+    fn from(x: DelimitedList<DELIMITER, SKIP_EMPTY>) -> Value {
+        Value::Bytes(x.0.join(DELIMITER.encode_utf8(&mut [0; 4])).into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn should_convert_non_empty_string() {
+        let value = Value::Bytes(b"foo".to_vec());
+        let s = from_value::<NonEmptyString>(value.clone());
+        assert_eq!(s.as_str(), "foo");
+        assert_eq!(Value::from(s), value);
+    }
+
+    #[test]
+    fn should_reject_empty_string() {
+        let value = Value::Bytes(Vec::new());
+        assert_eq!(
+            from_value_opt::<NonEmptyString>(value.clone())
+                .unwrap_err()
+                .0,
+            value
+        );
+    }
+
+    #[test]
+    fn should_reject_whitespace_only_string() {
+        let value = Value::Bytes(b" \t\n".to_vec());
+        assert!(from_value_opt::<NonEmptyString>(value).is_err());
+        assert_eq!(NonEmptyString::new(" \t\n"), None);
+    }
+
+    #[test]
+    fn should_reject_invalid_utf8() {
+        let value = Value::Bytes(vec![0xff, 0xfe]);
+        assert!(from_value_opt::<NonEmptyString>(value).is_err());
+    }
+
+    #[test]
+    fn should_truncate_c_string_at_nul() {
+        for (input, expected) in &[
+            (&b"abc\0\0"[..], "abc"),
+            (b"abc\0def", "abc"),
+            (b"abc", "abc"),
+            (b"\0abc", ""),
+            (b"", ""),
+        ] {
+            let CStrBytes(s) = from_value(Value::Bytes(input.to_vec()));
+            assert_eq!(s, *expected);
+        }
+        assert_eq!(
+            from_value::<String>(Value::Bytes(b"abc\0\0".to_vec())),
+            "abc\0\0"
+        );
+    }
+
+    #[test]
+    fn should_rollback_c_string_to_original_bytes() {
+        let value = Value::Bytes(b"abc\0\xff".to_vec());
+        let ir = CStrBytes::get_intermediate(value.clone()).unwrap();
+        assert_eq!(ir.rollback(), value);
+        assert!(from_value_opt::<CStrBytes>(Value::Bytes(b"\xff\0".to_vec())).is_err());
+        assert!(from_value_opt::<CStrBytes>(Value::Int(1)).is_err());
+    }
+
+    #[test]
+    fn should_unquote_csv_field() {
+        for (input, expected) in &[
+            (r#""hello, world""#, "hello, world"),
+            (r#""say ""hi""""#, r#"say "hi""#),
+            ("hello", "hello"),
+            ("", ""),
+        ] {
+            let value = Value::from(*input);
+            let CsvField(field) = from_value(value.clone());
+            assert_eq!(field, *expected);
+            assert_eq!(Value::from(CsvField(field)), value);
+        }
+        assert_eq!(from_value::<CsvField>(Value::from(r#""""#)).0, "");
+        assert_eq!(
+            from_value::<String>(Value::from(r#""hello""#)),
+            r#""hello""#
+        );
+    }
+
+    #[test]
+    fn should_reject_unbalanced_csv_quotes() {
+        for input in &[r#""hello"#, r#"hello""#, r#""a"b""#, r#"a""b"#, r#"""#] {
+            let value = Value::from(*input);
+            assert_eq!(
+                from_value_opt::<CsvField>(value.clone()).unwrap_err().0,
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn should_convert_char() {
+        assert_eq!(from_value::<char>(Value::from("A")), 'A');
+        assert_eq!(from_value::<char>(Value::from("\u{e9}")), '\u{e9}');
+        assert_eq!(Value::from('\u{1f600}'), Value::from("\u{1f600}"));
+        assert_eq!(from_value::<char>(Value::from('\u{1f600}')), '\u{1f600}');
+
+        for value in &[
+            Value::from(""),
+            Value::from("AB"),
+            Value::Bytes(vec![0xff]),
+            Value::Int(65),
+        ] {
+            assert_eq!(from_value_opt::<char>(value.clone()).unwrap_err().0, *value);
+        }
+    }
+
+    #[test]
+    fn should_convert_uuid_str() {
+        let uuid = Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+        let value = Value::from("67e55044-10b1-426f-9247-bb680e5fe0c8");
+        assert_eq!(from_value::<UuidStr>(value.clone()), UuidStr(uuid));
+        assert_eq!(
+            from_value::<UuidStr>(Value::from("67E55044-10B1-426F-9247-BB680E5FE0C8")),
+            UuidStr(uuid)
+        );
+        assert_eq!(Value::from(UuidStr(uuid)), value);
+
+        for input in &[
+            "67e5504410b1426f9247bb680e5fe0c8",
+            "{67e55044-10b1-426f-9247-bb680e5fe0c8}",
+            "urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "67e55044-10b1-426f-9247-bb680e5fe0cz",
+            "67e55044-10b1-426f-9247bbb680e5fe0c8",
+        ] {
+            let value = Value::from(*input);
+            assert_eq!(
+                from_value_opt::<UuidStr>(value.clone()).unwrap_err().0,
+                value
+            );
+        }
+        assert!(from_value_opt::<UuidStr>(Value::Bytes(uuid.as_bytes().to_vec())).is_err());
+    }
+
+    #[test]
+    fn should_split_delimited_list() {
+        let DelimitedList(list) = from_value::<DelimitedList>(Value::from("a,b,c"));
+        assert_eq!(list, vec!["a", "b", "c"]);
+
+        let DelimitedList(list) = from_value::<DelimitedList>(Value::from(""));
+        assert!(list.is_empty());
+
+        let DelimitedList(list) = from_value::<DelimitedList>(Value::from("a,,b,"));
+        assert_eq!(list, vec!["a", "", "b", ""]);
+
+        let DelimitedList(list) = from_value::<DelimitedList<',', true>>(Value::from("a,,b,"));
+        assert_eq!(list, vec!["a", "b"]);
+
+        let DelimitedList(list) = from_value::<DelimitedList<'\n'>>(Value::from("a,b\nc"));
+        assert_eq!(list, vec!["a,b", "c"]);
+
+        for value in &[Value::Bytes(vec![0xff]), Value::Int(1), Value::NULL] {
+            assert_eq!(
+                from_value_opt::<DelimitedList>(value.clone())
+                    .unwrap_err()
+                    .0,
+                *value
+            );
+        }
+    }
+
+    #[test]
+    fn should_join_delimited_list() {
+        let list = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        assert_eq!(
+            Value::from(DelimitedList::<','>(list.clone())),
+            Value::from("a,b,c")
+        );
+        assert_eq!(
+            Value::from(DelimitedList::<'\n'>(list)),
+            Value::from("a\nb\nc")
+        );
+        assert_eq!(
+            Value::from(DelimitedList::<','>(Vec::new())),
+            Value::from("")
+        );
+    }
+}
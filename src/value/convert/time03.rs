@@ -0,0 +1,134 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+//! This module implements conversion from/to `Value` for `time` v0.3 `Date` and `Time` types.
+
+use std::convert::TryFrom;
+
+use time03::{Date, Month, Time};
+
+use super::{
+    parse_mysql_datetime_string, parse_mysql_time_string, ConvIr, FromValue, FromValueError,
+    ParseIr, Value,
+};
+
+fn create_date(year: i32, month: u8, day: u8) -> Option<Date> {
+    let month = Month::try_from(month).ok()?;
+    Date::from_calendar_date(year, month, day).ok()
+}
+
+impl ConvIr<Date> for ParseIr<Date> {
+    fn new(value: Value) -> Result<ParseIr<Date>, FromValueError> {
+        let date = match value {
+            Value::Date(y, m, d, _, _, _, _) => create_date(y.into(), m, d),
+            Value::Bytes(ref bytes) => match parse_mysql_datetime_string(bytes) {
+                Some((y, m, d, _, _, _, _)) => create_date(y as i32, m as u8, d as u8),
+                None => None,
+            },
+            _ => None,
+        };
+
+        match date {
+            Some(output) => Ok(ParseIr { value, output }),
+            None => Err(FromValueError(value)),
+        }
+    }
+    fn commit(self) -> Date {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+/// `Value::Time` is converted only if it is non-negative and less than 24 hours
+/// (`Time` can't represent day-carrying values).
+impl ConvIr<Time> for ParseIr<Time> {
+    fn new(value: Value) -> Result<ParseIr<Time>, FromValueError> {
+        let time = match value {
+            Value::Time(false, 0, h, m, s, u) => Time::from_hms_micro(h, m, s, u).ok(),
+            Value::Bytes(ref bytes) => match parse_mysql_time_string(bytes) {
+                Some((false, h, m, s, u)) if h < 24 => {
+                    Time::from_hms_micro(h as u8, m as u8, s as u8, u).ok()
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+
+        match time {
+            Some(output) => Ok(ParseIr { value, output }),
+            None => Err(FromValueError(value)),
+        }
+    }
+    fn commit(self) -> Time {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl FromValue for Date {
+    type Intermediate = ParseIr<Date>;
+}
+
+impl FromValue for Time {
+    type Intermediate = ParseIr<Time>;
+}
+
+impl From<Date> for Value {
+    fn from(x: Date) -> Value {
+        if 1000 > x.year() || x.year() > 9999 {
+            panic!("Year `{}` not in supported range [1000, 9999]", x.year())
+        }
+        Value::Date(x.year() as u16, x.month().into(), x.day(), 0, 0, 0, 0)
+    }
+}
+
+impl From<Time> for Value {
+    fn from(x: Time) -> Value {
+        Value::Time(false, 0, x.hour(), x.minute(), x.second(), x.microsecond())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time03::{Date, Month, Time};
+
+    use crate::value::convert::{from_value, from_value_opt};
+    use crate::value::Value;
+
+    #[test]
+    fn date_roundtrip() {
+        let date = Date::from_calendar_date(2019, Month::November, 27).unwrap();
+        let value = Value::Date(2019, 11, 27, 0, 0, 0, 0);
+        assert_eq!(Value::from(date), value);
+        assert_eq!(from_value::<Date>(value), date);
+        assert_eq!(from_value::<Date>(Value::from("2019-11-27")), date);
+        assert!(from_value_opt::<Date>(Value::Date(2019, 2, 30, 0, 0, 0, 0)).is_err());
+    }
+
+    #[test]
+    fn time_roundtrip() {
+        let time = Time::from_hms_micro(12, 30, 1, 123_456).unwrap();
+        let value = Value::Time(false, 0, 12, 30, 1, 123_456);
+        assert_eq!(Value::from(time), value);
+        assert_eq!(from_value::<Time>(value), time);
+        assert_eq!(from_value::<Time>(Value::from("12:30:01.123456")), time);
+    }
+
+    #[test]
+    fn time_should_reject_out_of_day_values() {
+        let value = Value::Time(false, 1, 0, 0, 0, 0);
+        assert_eq!(from_value_opt::<Time>(value.clone()).unwrap_err().0, value);
+        assert!(from_value_opt::<Time>(Value::Time(true, 0, 1, 0, 0, 0)).is_err());
+        assert!(from_value_opt::<Time>(Value::from("25:00:00")).is_err());
+        assert!(from_value_opt::<Time>(Value::from("-01:00:00")).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+//! This module implements conversion from/to `Value` for `chrono_tz::Tz`.
+
+use chrono_tz::Tz;
+
+use std::str::{from_utf8, FromStr};
+
+use super::{ConvIr, FromValue, FromValueError, ParseIr, Value};
+
+/// Bytes are parsed using `Tz::from_str`, so names are case-sensitive. Aliases (links) are
+/// preserved as is, i.e. `"US/Eastern"` is not normalized to `"America/New_York"`.
+impl ConvIr<Tz> for ParseIr<Tz> {
+    fn new(value: Value) -> Result<ParseIr<Tz>, FromValueError> {
+        let tz = match value {
+            Value::Bytes(ref bytes) => from_utf8(bytes).ok().and_then(|x| Tz::from_str(x).ok()),
+            _ => None,
+        };
+
+        match tz {
+            Some(output) => Ok(ParseIr { value, output }),
+            None => Err(FromValueError(value)),
+        }
+    }
+    fn commit(self) -> Tz {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl FromValue for Tz {
+    type Intermediate = ParseIr<Tz>;
+}
+
+impl From<Tz> for Value {
+    fn from(x: Tz) -> Value {
+        Value::Bytes(x.name().as_bytes().to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::Tz;
+
+    use crate::value::convert::{from_value, from_value_opt};
+    use crate::value::Value;
+
+    #[test]
+    fn tz_roundtrip() {
+        let value = Value::from("Europe/Paris");
+        let tz = from_value::<Tz>(value.clone());
+        assert_eq!(tz, chrono_tz::Europe::Paris);
+        assert_eq!(Value::from(tz), value);
+
+        let value = Value::from("US/Eastern");
+        assert_eq!(Value::from(from_value::<Tz>(value.clone())), value);
+    }
+
+    #[test]
+    fn should_reject_invalid_tz() {
+        for input in &["Europe/Atlantis", "europe/paris", ""] {
+            let value = Value::from(*input);
+            assert_eq!(from_value_opt::<Tz>(value.clone()).unwrap_err().0, value);
+        }
+        assert!(from_value_opt::<Tz>(Value::Int(0)).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+//! This module implements conversion from/to `Value` for `semver::Version`.
+
+use semver::Version;
+
+use std::str::from_utf8;
+
+use super::{ConvIr, FromValue, FromValueError, ParseIr, Value};
+
+impl ConvIr<Version> for ParseIr<Version> {
+    fn new(value: Value) -> Result<ParseIr<Version>, FromValueError> {
+        let version = match value {
+            Value::Bytes(ref bytes) => from_utf8(bytes).ok().and_then(|s| Version::parse(s).ok()),
+            _ => None,
+        };
+
+        match version {
+            Some(output) => Ok(ParseIr { value, output }),
+            None => Err(FromValueError(value)),
+        }
+    }
+    fn commit(self) -> Version {
+        self.output
+    }
+    fn rollback(self) -> Value {
+        self.value
+    }
+}
+
+impl FromValue for Version {
+    type Intermediate = ParseIr<Version>;
+}
+
+impl From<Version> for Value {
+    fn from(x: Version) -> Value {
+        Value::Bytes(x.to_string().into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use semver::Version;
+
+    use crate::value::convert::{from_value, from_value_opt};
+    use crate::value::Value;
+
+    #[test]
+    fn version_roundtrip() {
+        let value = Value::from("1.2.3-alpha+build");
+        let version = from_value::<Version>(value.clone());
+        assert_eq!(version.major, 1);
+        assert_eq!(version.minor, 2);
+        assert_eq!(version.patch, 3);
+        assert_eq!(version.pre.as_str(), "alpha");
+        assert_eq!(version.build.as_str(), "build");
+        assert_eq!(Value::from(version), value);
+    }
+
+    #[test]
+    fn should_reject_invalid_version() {
+        for input in &["1.2", "v1.2.3", "1.2.3.4", ""] {
+            let value = Value::from(*input);
+            assert_eq!(
+                from_value_opt::<Version>(value.clone()).unwrap_err().0,
+                value
+            );
+        }
+        assert!(from_value_opt::<Version>(Value::Int(1)).is_err());
+    }
+}
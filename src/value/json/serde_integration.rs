@@ -11,9 +11,43 @@ use crate::value::convert::{ConvIr, FromValue, FromValueError};
 use crate::value::Value;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use serde_json::{self, Value as Json};
+use serde_json::{self, Number, Value as Json};
 use std::str::{from_utf8, from_utf8_unchecked};
 
+impl Value {
+    /// Returns this value as a JSON number.
+    ///
+    /// Integers are represented exactly (i.e. `Value::UInt(u64::MAX)` is not rounded through
+    /// `f64`). Returns `None` for non-numeric values and for non-finite floats.
+    pub fn to_json_number(&self) -> Option<Number> {
+        match *self {
+            Value::Int(x) => Some(Number::from(x)),
+            Value::UInt(x) => Some(Number::from(x)),
+            Value::Float(x) => Number::from_f64(x),
+            _ => None,
+        }
+    }
+
+    /// Returns JSON representation of this value.
+    ///
+    /// Numbers are mapped to JSON numbers (non-finite floats to `null`), bytes to a string
+    /// (base64-encoded if not a valid UTF-8), dates and times to strings in MySql format.
+    pub(crate) fn to_json(&self) -> Json {
+        match *self {
+            Value::NULL => Json::Null,
+            Value::Int(..) | Value::UInt(..) | Value::Float(..) => self
+                .to_json_number()
+                .map(Json::Number)
+                .unwrap_or(Json::Null),
+            Value::Bytes(ref bytes) => match from_utf8(bytes) {
+                Ok(string) => Json::from(string),
+                Err(_) => Json::from(base64::encode(bytes)),
+            },
+            Value::Date(..) | Value::Time(..) => Json::from(self.as_sql(false).trim_matches('\'')),
+        }
+    }
+}
+
 impl From<Json> for Value {
     fn from(x: Json) -> Value {
         Value::Bytes(serde_json::to_string(&x).unwrap().into())
@@ -103,3 +137,63 @@ impl ConvIr<Json> for JsonIr {
 impl FromValue for Json {
     type Intermediate = JsonIr;
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{self, Number, Value as Json};
+
+    use crate::value::convert::{from_value, from_value_opt};
+    use crate::value::Value;
+
+    #[test]
+    fn should_serialize_integers_exactly() {
+        let json = serde_json::to_string(&Value::UInt(u64::MAX).to_json()).unwrap();
+        assert_eq!(json, "18446744073709551615");
+        let parsed: Json = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.as_u64(), Some(u64::MAX));
+
+        let json = serde_json::to_string(&Value::Int(i64::MIN).to_json()).unwrap();
+        let parsed: Json = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.as_i64(), Some(i64::MIN));
+
+        // 2^53 + 1 is not representable as f64
+        let json = serde_json::to_string(&Value::UInt(9_007_199_254_740_993).to_json()).unwrap();
+        assert_eq!(json, "9007199254740993");
+    }
+
+    #[test]
+    fn should_convert_to_json_number() {
+        assert_eq!(Value::Int(-1).to_json_number(), Some(Number::from(-1)));
+        assert_eq!(Value::Float(1.5).to_json_number(), Number::from_f64(1.5));
+        assert_eq!(Value::Float(1.5).to_json(), Json::from(1.5));
+        assert_eq!(Value::Float(f64::NAN).to_json_number(), None);
+        assert_eq!(Value::Float(f64::INFINITY).to_json(), Json::Null);
+        assert_eq!(Value::from("1").to_json_number(), None);
+        assert_eq!(Value::NULL.to_json_number(), None);
+    }
+
+    #[test]
+    fn should_roundtrip_json() {
+        let json = serde_json::json!({
+            "foo": [1, "two", null, {"bar": [true, 1.5]}],
+            "baz": {},
+        });
+        let value = Value::from(json.clone());
+        assert_eq!(
+            value,
+            Value::from(r#"{"baz":{},"foo":[1,"two",null,{"bar":[true,1.5]}]}"#)
+        );
+        assert_eq!(from_value::<Json>(value), json);
+    }
+
+    #[test]
+    fn should_rollback_invalid_json() {
+        for value in &[
+            Value::from("{\"foo\":"),
+            Value::Bytes(vec![0xff]),
+            Value::Int(1),
+        ] {
+            assert_eq!(from_value_opt::<Json>(value.clone()).unwrap_err().0, *value);
+        }
+    }
+}
@@ -9,6 +9,7 @@
 use byteorder::{LittleEndian as LE, ReadBytesExt};
 
 use std::fmt;
+use std::hash::Hasher;
 use std::io;
 use std::str::from_utf8;
 
@@ -16,10 +17,13 @@ use crate::constants::{ColumnFlags, ColumnType};
 use crate::io::ReadMysqlExt;
 use crate::misc::lenenc_int_len;
 use crate::packets::{Column, NullBitmap};
+use crate::value::convert::{from_value_opt, FromValueError};
 use crate::value::Value::*;
 
 pub mod convert;
 pub mod json;
+pub mod normalized;
+pub mod visitor;
 
 /// Side of MySql value serialization.
 pub trait SerializationSide {
@@ -124,7 +128,73 @@ fn escaped(input: &str, no_backslash_escape: bool) -> String {
     output
 }
 
+/// Kind of a `Value`, i.e. its variant without payload.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ValueKind {
+    NULL,
+    Bytes,
+    Int,
+    UInt,
+    Float,
+    Date,
+    Time,
+}
+
 impl Value {
+    /// Returns kind of this value.
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            Value::NULL => ValueKind::NULL,
+            Value::Bytes(_) => ValueKind::Bytes,
+            Value::Int(_) => ValueKind::Int,
+            Value::UInt(_) => ValueKind::UInt,
+            Value::Float(_) => ValueKind::Float,
+            Value::Date(..) => ValueKind::Date,
+            Value::Time(..) => ValueKind::Time,
+        }
+    }
+
+    /// Coerces this value to the given kind.
+    ///
+    /// Runtime analogue of `FromValue`: value is converted as `i64`, `u64`, `f64`,
+    /// `NaiveDateTime` or `time::Duration` for `Int`, `UInt`, `Float`, `Date` and `Time` kinds
+    /// respectively, so conversion rules are the same. Coercion to `Bytes` gives textual
+    /// representation of the value. `NULL` is passed through for every kind, while coercion
+    /// of a non-NULL value to `NULL` is an error.
+    pub fn try_parse_as(&self, kind: ValueKind) -> Result<Value, FromValueError> {
+        if self.kind() == kind || *self == Value::NULL {
+            return Ok(self.clone());
+        }
+
+        match kind {
+            ValueKind::NULL => Err(FromValueError(self.clone())),
+            ValueKind::Bytes => {
+                let text = match *self {
+                    Value::Int(x) => x.to_string(),
+                    Value::UInt(x) => x.to_string(),
+                    Value::Float(x) => x.to_string(),
+                    _ => self.as_sql(false).trim_matches('\'').into(),
+                };
+                Ok(Value::Bytes(text.into_bytes()))
+            }
+            ValueKind::Int => from_value_opt::<i64>(self.clone()).map(Value::Int),
+            ValueKind::UInt => from_value_opt::<u64>(self.clone()).map(Value::UInt),
+            ValueKind::Float => from_value_opt::<f64>(self.clone()).map(Value::Float),
+            ValueKind::Date => {
+                from_value_opt::<chrono::NaiveDateTime>(self.clone()).map(Value::from)
+            }
+            ValueKind::Time => from_value_opt::<time::Duration>(self.clone()).map(Value::from),
+        }
+    }
+
+    /// Shrinks capacity of `Value::Bytes` to its length. No-op for other variants.
+    pub fn shrink_to_fit(&mut self) {
+        if let Value::Bytes(bytes) = self {
+            bytes.shrink_to_fit();
+        }
+    }
+
     /// Returns length in binary serialized form.
     #[inline]
     pub fn bin_len(&self) -> usize {
@@ -185,6 +255,119 @@ impl Value {
         }
     }
 
+    /// Compares values, treating numeric variants by their mathematical value.
+    ///
+    /// Unlike the derived `PartialEq`, `Int`, `UInt` and `Float` are comparable with each other,
+    /// so `Int(1)`, `UInt(1)` and `Float(1.0)` are equal, while `Int(-1)` and `UInt(u64::MAX)`
+    /// are not. Float is equal to an integer only if it is integral and represents exactly the
+    /// same number (note that large integers may not be representable as `f64`). Other variants
+    /// are compared structurally.
+    pub fn value_eq(&self, other: &Value) -> bool {
+        fn float_eq_int(x: f64, y: i128) -> bool {
+            x.fract() == 0.0 && x as i128 == y
+        }
+
+        match (self, other) {
+            (Value::Int(x), Value::Int(y)) => x == y,
+            (Value::UInt(x), Value::UInt(y)) => x == y,
+            (Value::Float(x), Value::Float(y)) => x == y,
+            (Value::Int(x), Value::UInt(y)) | (Value::UInt(y), Value::Int(x)) => {
+                i128::from(*x) == i128::from(*y)
+            }
+            (Value::Float(x), Value::Int(y)) | (Value::Int(y), Value::Float(x)) => {
+                float_eq_int(*x, i128::from(*y))
+            }
+            (Value::Float(x), Value::UInt(y)) | (Value::UInt(y), Value::Float(x)) => {
+                float_eq_int(*x, i128::from(*y))
+            }
+            (x, y) => x == y,
+        }
+    }
+
+    /// Compares values, normalizing numeric and textual representations.
+    ///
+    /// Opt-in comparison for assertions. Rules:
+    ///
+    /// * if exactly one of values is `Bytes`, then it is coerced to the kind of the other value
+    ///   using `Value::try_parse_as` (so `Bytes(b"1")` equals `Int(1)`, `UInt(1)` and
+    ///   `Float(1.0)`, and `Bytes(b"2019-01-01 00:00:00")` equals the corresponding `Date`),
+    ///   values are not equal if coercion fails;
+    /// * two `Bytes` values are compared structurally (`"1"` is not equal to `"01"`);
+    /// * otherwise values are compared using `Value::value_eq`.
+    ///
+    /// Use `Value::loosely_eq_within` to compare floats with a tolerance.
+    pub fn loosely_eq(&self, other: &Value) -> bool {
+        self.loosely_eq_within(other, 0.0)
+    }
+
+    /// Same as `Value::loosely_eq`, but numbers are equal if at least one of them is a float and
+    /// they differ by no more than `tolerance`.
+    pub fn loosely_eq_within(&self, other: &Value, tolerance: f64) -> bool {
+        fn as_f64(value: &Value) -> Option<f64> {
+            match *value {
+                Value::Int(x) => Some(x as f64),
+                Value::UInt(x) => Some(x as f64),
+                Value::Float(x) => Some(x),
+                _ => None,
+            }
+        }
+
+        match (self, other) {
+            (Value::Bytes(_), Value::Bytes(_)) => self == other,
+            (Value::Bytes(_), _) => self
+                .try_parse_as(other.kind())
+                .map(|x| x.loosely_eq_within(other, tolerance))
+                .unwrap_or(false),
+            (_, Value::Bytes(_)) => other.loosely_eq_within(self, tolerance),
+            (Value::Float(_), _) | (_, Value::Float(_)) if tolerance > 0.0 => {
+                match (as_f64(self), as_f64(other)) {
+                    (Some(x), Some(y)) => (x - y).abs() <= tolerance,
+                    _ => false,
+                }
+            }
+            (x, y) => x.value_eq(y),
+        }
+    }
+
+    /// Feeds this value into the given `Hasher`.
+    ///
+    /// Variants are hashed structurally; floats are hashed by their bit pattern.
+    pub(crate) fn hash_into<H: Hasher>(&self, state: &mut H) {
+        match *self {
+            Value::NULL => state.write_u8(0),
+            Value::Bytes(ref bytes) => {
+                state.write_u8(1);
+                state.write_usize(bytes.len());
+                state.write(bytes);
+            }
+            Value::Int(x) => {
+                state.write_u8(2);
+                state.write_i64(x);
+            }
+            Value::UInt(x) => {
+                state.write_u8(3);
+                state.write_u64(x);
+            }
+            Value::Float(x) => {
+                state.write_u8(4);
+                state.write_u64(x.to_bits());
+            }
+            Value::Date(y, m, d, h, i, s, u) => {
+                state.write_u8(5);
+                state.write_u16(y);
+                state.write(&[m, d, h, i, s]);
+                state.write_u32(u);
+            }
+            Value::Time(neg, d, h, i, s, u) => {
+                state.write_u8(6);
+                state.write_u8(neg as u8);
+                state.write_u32(d);
+                state.write(&[h, i, s]);
+                state.write_u32(u);
+            }
+        }
+    }
+
     fn read_text(input: &mut &[u8]) -> io::Result<Value> {
         if input.is_empty() {
             Err(io::Error::new(
@@ -402,7 +585,140 @@ impl fmt::Debug for Value {
 
 #[cfg(test)]
 mod test {
-    use crate::value::Value;
+    use crate::value::{Value, ValueKind};
+
+    #[test]
+    fn should_compare_numeric_values_by_magnitude() {
+        assert!(Value::Int(1).value_eq(&Value::UInt(1)));
+        assert!(Value::UInt(1).value_eq(&Value::Int(1)));
+        assert!(!Value::Int(-1).value_eq(&Value::UInt(u64::MAX)));
+        assert!(Value::Float(1.0).value_eq(&Value::Int(1)));
+        assert!(Value::UInt(2).value_eq(&Value::Float(2.0)));
+        assert!(!Value::Float(1.5).value_eq(&Value::Int(1)));
+        assert!(!Value::Float(f64::NAN).value_eq(&Value::Float(f64::NAN)));
+        assert!(!Value::Float(u64::MAX as f64).value_eq(&Value::UInt(u64::MAX)));
+        assert_ne!(Value::Int(1), Value::UInt(1));
+    }
+
+    #[test]
+    fn should_compare_other_values_structurally() {
+        assert!(Value::NULL.value_eq(&Value::NULL));
+        assert!(Value::from("1").value_eq(&Value::from("1")));
+        assert!(!Value::from("1").value_eq(&Value::Int(1)));
+        assert!(!Value::NULL.value_eq(&Value::Int(0)));
+        assert!(Value::Date(2019, 1, 1, 0, 0, 0, 0).value_eq(&Value::Date(2019, 1, 1, 0, 0, 0, 0)));
+    }
+
+    #[test]
+    fn should_compare_values_loosely() {
+        assert!(Value::Int(1).loosely_eq(&Value::UInt(1)));
+        assert!(Value::from("1").loosely_eq(&Value::Int(1)));
+        assert!(Value::UInt(1).loosely_eq(&Value::from("1")));
+        assert!(Value::from("1").loosely_eq(&Value::Float(1.0)));
+        assert!(Value::from("-1").loosely_eq(&Value::Int(-1)));
+        assert!(!Value::from("-1").loosely_eq(&Value::UInt(u64::MAX)));
+        assert!(!Value::from("1.5").loosely_eq(&Value::Int(1)));
+        assert!(!Value::from("foo").loosely_eq(&Value::Int(0)));
+        assert!(Value::from("foo").loosely_eq(&Value::from("foo")));
+        assert!(!Value::from("1").loosely_eq(&Value::from("01")));
+        assert!(
+            Value::from("2019-01-01 12:00:00").loosely_eq(&Value::Date(2019, 1, 1, 12, 0, 0, 0))
+        );
+        assert!(Value::from("-01:00:00").loosely_eq(&Value::Time(true, 0, 1, 0, 0, 0)));
+        assert!(Value::NULL.loosely_eq(&Value::NULL));
+        assert!(!Value::NULL.loosely_eq(&Value::from("NULL")));
+        assert!(!Value::from("").loosely_eq(&Value::NULL));
+    }
+
+    #[test]
+    fn should_compare_floats_within_tolerance() {
+        assert!(!Value::Float(0.1 + 0.2).loosely_eq(&Value::Float(0.3)));
+        assert!(Value::Float(0.1 + 0.2).loosely_eq_within(&Value::Float(0.3), 1e-9));
+        assert!(Value::from("0.3").loosely_eq_within(&Value::Float(0.1 + 0.2), 1e-9));
+        assert!(Value::Int(1).loosely_eq_within(&Value::Float(1.0005), 1e-3));
+        assert!(!Value::Int(1).loosely_eq_within(&Value::Float(1.5), 1e-3));
+        assert!(!Value::Int(1).loosely_eq_within(&Value::Int(2), 1.5));
+    }
+
+    #[test]
+    fn should_parse_value_as_kind() {
+        assert_eq!(
+            Value::from("42").try_parse_as(ValueKind::Int).unwrap(),
+            Value::Int(42)
+        );
+        assert_eq!(
+            Value::from("42").try_parse_as(ValueKind::UInt).unwrap(),
+            Value::UInt(42)
+        );
+        assert_eq!(
+            Value::from("1.5").try_parse_as(ValueKind::Float).unwrap(),
+            Value::Float(1.5)
+        );
+        assert_eq!(
+            Value::from("2019-01-02 03:04:05")
+                .try_parse_as(ValueKind::Date)
+                .unwrap(),
+            Value::Date(2019, 1, 2, 3, 4, 5, 0)
+        );
+        assert_eq!(
+            Value::from("-01:02:03")
+                .try_parse_as(ValueKind::Time)
+                .unwrap(),
+            Value::Time(true, 0, 1, 2, 3, 0)
+        );
+        assert_eq!(
+            Value::Int(-7).try_parse_as(ValueKind::Bytes).unwrap(),
+            Value::from("-7")
+        );
+        assert_eq!(
+            Value::Date(2019, 1, 2, 0, 0, 0, 0)
+                .try_parse_as(ValueKind::Bytes)
+                .unwrap(),
+            Value::from("2019-01-02")
+        );
+        assert_eq!(
+            Value::Int(1).try_parse_as(ValueKind::UInt).unwrap(),
+            Value::UInt(1)
+        );
+        assert_eq!(
+            Value::NULL.try_parse_as(ValueKind::Int).unwrap(),
+            Value::NULL
+        );
+        assert_eq!(
+            Value::Int(1).try_parse_as(ValueKind::Int).unwrap(),
+            Value::Int(1)
+        );
+    }
+
+    #[test]
+    fn should_fail_to_parse_value_as_kind() {
+        let value = Value::from("foo");
+        assert_eq!(value.try_parse_as(ValueKind::Int).unwrap_err().0, value);
+        assert!(Value::Int(-1).try_parse_as(ValueKind::UInt).is_err());
+        assert!(Value::from("2019-13-01")
+            .try_parse_as(ValueKind::Date)
+            .is_err());
+        assert!(Value::Int(1).try_parse_as(ValueKind::NULL).is_err());
+    }
+
+    #[test]
+    fn should_shrink_bytes_to_fit() {
+        let mut bytes = Vec::with_capacity(1024);
+        bytes.extend_from_slice(b"foo");
+        let mut value = Value::Bytes(bytes);
+        value.shrink_to_fit();
+        match value {
+            Value::Bytes(ref bytes) => {
+                assert_eq!(bytes, b"foo");
+                assert!(bytes.capacity() < 1024);
+            }
+            _ => unreachable!(),
+        }
+
+        let mut value = Value::Int(1);
+        value.shrink_to_fit();
+        assert_eq!(value, Value::Int(1));
+    }
 
     #[test]
     fn should_escape_string() {
@@ -414,12 +730,50 @@ mod test {
         assert_eq!(r"'?p??\0?p??'", Value::from("?p??\x00?p??").as_sql(false));
     }
 
+    #[test]
+    fn should_escape_string_without_backslashes() {
+        assert_eq!(r"'?p??''?p??'", Value::from("?p??'?p??").as_sql(true));
+        assert_eq!(r"'?p??\?p??'", Value::from("?p??\\?p??").as_sql(true));
+        assert_eq!(r#"'?p??"?p??'"#, Value::from("?p??\"?p??").as_sql(true));
+        assert_eq!("'?p??\x00?p??'", Value::from("?p??\x00?p??").as_sql(true));
+    }
+
+    #[test]
+    fn should_render_values_as_sql() {
+        assert_eq!(Value::NULL.as_sql(false), "NULL");
+        assert_eq!(Value::Int(-42).as_sql(false), "-42");
+        assert_eq!(Value::UInt(u64::MAX).as_sql(false), "18446744073709551615");
+        assert_eq!(Value::Float(1.5).as_sql(false), "1.5");
+        assert_eq!(
+            Value::Bytes(vec![0xff, 0x00, b'\'']).as_sql(true),
+            "0xFF0027"
+        );
+        assert_eq!(
+            Value::Date(2019, 11, 27, 0, 0, 0, 0).as_sql(false),
+            "'2019-11-27'"
+        );
+        assert_eq!(
+            Value::Date(2019, 11, 27, 12, 30, 1, 0).as_sql(false),
+            "'2019-11-27 12:30:01'"
+        );
+        assert_eq!(
+            Value::Date(2019, 11, 27, 12, 30, 1, 5).as_sql(false),
+            "'2019-11-27 12:30:01.000005'"
+        );
+        assert_eq!(
+            Value::Time(false, 0, 1, 2, 3, 0).as_sql(false),
+            "'001:02:03'"
+        );
+        assert_eq!(
+            Value::Time(true, 1, 2, 3, 4, 5).as_sql(false),
+            "'-026:03:04.000005'"
+        );
+    }
+
     #[cfg(feature = "nightly")]
     mod benches {
         use crate::constants::ColumnType;
-        use crate::io::WriteMysqlExt;
-        use crate::packets::{column_from_payload, Column};
-        use crate::packets::{ComStmtExecuteRequestBuilder, NullBitmap};
+        use crate::packets::{Column, ComStmtExecuteRequestBuilder, NullBitmap};
         use crate::value::{ClientSide, Value};
 
         #[bench]
@@ -464,17 +818,6 @@ mod test {
         #[cfg(feature = "nightly")]
         #[bench]
         fn bench_parse_bin_row(bencher: &mut test::Bencher) {
-            fn col(name: &str, ty: ColumnType) -> Column {
-                let mut payload = b"\x00def".to_vec();
-                for _ in 0..5 {
-                    payload.write_lenenc_str(name.as_bytes()).unwrap();
-                }
-                payload.extend_from_slice(&b"_\x2d\x00\xff\xff\xff\xff"[..]);
-                payload.push(ty as u8);
-                payload.extend_from_slice(&b"\x00\x00\x00"[..]);
-                column_from_payload(payload).unwrap()
-            }
-
             let values = vec![
                 Value::Bytes(b"12.3456789".to_vec()),
                 Value::Int(0xF0),
@@ -514,7 +857,7 @@ mod test {
             let meta_len = values.len() * 2;
             let columns = body[meta_offset..(meta_offset + meta_len)]
                 .chunks(2)
-                .map(|meta| col("foo", ColumnType::from(meta[0])))
+                .map(|meta| Column::new(b"foo", ColumnType::from(meta[0])))
                 .collect::<Vec<_>>();
 
             let mut data = vec![0x00];
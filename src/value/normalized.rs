@@ -0,0 +1,143 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+//! This module implements representation-independent hashing of `Value`.
+
+use lexical::parse;
+
+use std::hash::{Hash, Hasher};
+
+use super::Value;
+
+/// `Value` wrapper whose `Hash` and `Eq` ignore numeric representation (e.g. for dedup keys).
+///
+/// Values are compared in a canonical form:
+///
+/// * `Int`, `UInt` and integral `Float` (that fits `i128`) are integers, so `Int(5)`, `UInt(5)`
+///   and `Float(5.0)` are equal;
+/// * `Bytes` holding a decimal integer (e.g. `b"5"`, `b"-5"`) are integers too, so they are
+///   equal to `Int(5)` and `Int(-5)` respectively, other bytes are compared as is (e.g. `b"5.5"`
+///   is not equal to `Float(5.5)`);
+/// * non-integral floats are compared by their bit pattern (so `NaN` is equal to itself);
+/// * other variants are compared structurally.
+#[derive(Debug, Clone)]
+pub struct NormalizedValue(pub Value);
+
+#[derive(PartialEq, Eq, Hash)]
+enum Canonical<'a> {
+    Null,
+    Int(i128),
+    Float(u64),
+    Bytes(&'a [u8]),
+    Date(u16, u8, u8, u8, u8, u8, u32),
+    Time(bool, u32, u8, u8, u8, u32),
+}
+
+impl NormalizedValue {
+    fn canonical(&self) -> Canonical<'_> {
+        match self.0 {
+            Value::NULL => Canonical::Null,
+            Value::Int(x) => Canonical::Int(x.into()),
+            Value::UInt(x) => Canonical::Int(x.into()),
+            Value::Float(x) => {
+                // i128 bounds are exactly representable as f64
+                if x.fract() == 0.0 && x >= i128::MIN as f64 && x < i128::MAX as f64 {
+                    Canonical::Int(x as i128)
+                } else {
+                    Canonical::Float(x.to_bits())
+                }
+            }
+            Value::Bytes(ref bytes) => match parse::<i128, _>(bytes) {
+                Ok(x) => Canonical::Int(x),
+                Err(_) => Canonical::Bytes(bytes),
+            },
+            Value::Date(y, m, d, h, i, s, u) => Canonical::Date(y, m, d, h, i, s, u),
+            Value::Time(neg, d, h, i, s, u) => Canonical::Time(neg, d, h, i, s, u),
+        }
+    }
+}
+
+impl PartialEq for NormalizedValue {
+    fn eq(&self, other: &NormalizedValue) -> bool {
+        self.canonical() == other.canonical()
+    }
+}
+
+impl Eq for NormalizedValue {}
+
+impl Hash for NormalizedValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical().hash(state)
+    }
+}
+
+impl From<Value> for NormalizedValue {
+    fn from(x: Value) -> NormalizedValue {
+        NormalizedValue(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::NormalizedValue;
+    use crate::value::Value;
+
+    #[test]
+    fn should_dedup_numeric_representations() {
+        let keys = [
+            Value::Int(5),
+            Value::UInt(5),
+            Value::Bytes(b"5".to_vec()),
+            Value::Float(5.0),
+            Value::Int(-5),
+            Value::Bytes(b"-5".to_vec()),
+            Value::UInt(u64::MAX),
+            Value::Bytes(b"18446744073709551615".to_vec()),
+        ];
+        let set = keys
+            .iter()
+            .cloned()
+            .map(NormalizedValue)
+            .collect::<HashSet<_>>();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&NormalizedValue(Value::Int(5))));
+        assert!(set.contains(&NormalizedValue(Value::Int(-5))));
+        assert!(set.contains(&NormalizedValue(Value::UInt(u64::MAX))));
+        assert_ne!(Value::Int(5), Value::Bytes(b"5".to_vec()));
+    }
+
+    #[test]
+    fn should_keep_other_values_distinct() {
+        let values = [
+            Value::NULL,
+            Value::Int(0),
+            Value::Bytes(Vec::new()),
+            Value::Bytes(b"foo".to_vec()),
+            Value::Bytes(b"5.5".to_vec()),
+            Value::Float(5.5),
+            Value::Date(2019, 1, 1, 0, 0, 0, 0),
+            Value::Time(false, 0, 0, 0, 0, 0),
+        ];
+        let set = values
+            .iter()
+            .cloned()
+            .map(NormalizedValue)
+            .collect::<HashSet<_>>();
+        assert_eq!(set.len(), values.len());
+        assert_eq!(
+            NormalizedValue(Value::Float(f64::NAN)),
+            NormalizedValue(Value::Float(f64::NAN))
+        );
+        assert_eq!(
+            NormalizedValue(Value::Float(-0.0)),
+            NormalizedValue(Value::Int(0))
+        );
+    }
+}
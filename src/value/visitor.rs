@@ -0,0 +1,132 @@
+// Copyright (c) 2017 Anatoly Ikorsky
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+//! This module implements streaming consumption of `Value` without cloning.
+
+use super::Value;
+
+/// Visitor over variants of a `Value` (see `Value::accept`).
+///
+/// Useful for serializers (JSON, CSV, ...) that consume values one by one. Payload is passed
+/// by value for scalars and by reference for bytes, so nothing is cloned or allocated.
+pub trait ValueVisitor {
+    /// Called for `Value::NULL`.
+    fn visit_null(&mut self);
+
+    /// Called for `Value::Int`.
+    fn visit_int(&mut self, x: i64);
+
+    /// Called for `Value::UInt`.
+    fn visit_uint(&mut self, x: u64);
+
+    /// Called for `Value::Float`.
+    fn visit_float(&mut self, x: f64);
+
+    /// Called for `Value::Bytes`.
+    fn visit_bytes(&mut self, x: &[u8]);
+
+    /// Called for `Value::Date(year, month, day, hour, minutes, seconds, micro seconds)`.
+    #[allow(clippy::too_many_arguments)]
+    fn visit_date(&mut self, year: u16, month: u8, day: u8, hour: u8, min: u8, sec: u8, micro: u32);
+
+    /// Called for `Value::Time(is negative, days, hours, minutes, seconds, micro seconds)`.
+    fn visit_time(&mut self, is_neg: bool, days: u32, hours: u8, min: u8, sec: u8, micro: u32);
+}
+
+impl Value {
+    /// Passes this value to the corresponding method of `visitor`.
+    pub fn accept<V: ValueVisitor>(&self, visitor: &mut V) {
+        match *self {
+            Value::NULL => visitor.visit_null(),
+            Value::Int(x) => visitor.visit_int(x),
+            Value::UInt(x) => visitor.visit_uint(x),
+            Value::Float(x) => visitor.visit_float(x),
+            Value::Bytes(ref x) => visitor.visit_bytes(x),
+            Value::Date(y, m, d, h, i, s, u) => visitor.visit_date(y, m, d, h, i, s, u),
+            Value::Time(neg, d, h, i, s, u) => visitor.visit_time(neg, d, h, i, s, u),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::ValueVisitor;
+    use crate::value::{Value, ValueKind};
+
+    #[derive(Default)]
+    struct Histogram {
+        kinds: HashMap<ValueKind, usize>,
+        bytes: usize,
+    }
+
+    impl Histogram {
+        fn add(&mut self, kind: ValueKind) {
+            *self.kinds.entry(kind).or_insert(0) += 1;
+        }
+    }
+
+    impl ValueVisitor for Histogram {
+        fn visit_null(&mut self) {
+            self.add(ValueKind::NULL);
+        }
+        fn visit_int(&mut self, _: i64) {
+            self.add(ValueKind::Int);
+        }
+        fn visit_uint(&mut self, _: u64) {
+            self.add(ValueKind::UInt);
+        }
+        fn visit_float(&mut self, _: f64) {
+            self.add(ValueKind::Float);
+        }
+        fn visit_bytes(&mut self, x: &[u8]) {
+            self.add(ValueKind::Bytes);
+            self.bytes += x.len();
+        }
+        fn visit_date(&mut self, _: u16, _: u8, _: u8, _: u8, _: u8, _: u8, _: u32) {
+            self.add(ValueKind::Date);
+        }
+        fn visit_time(&mut self, _: bool, _: u32, _: u8, _: u8, _: u8, _: u32) {
+            self.add(ValueKind::Time);
+        }
+    }
+
+    #[test]
+    fn should_visit_values() {
+        let values = [
+            Value::NULL,
+            Value::Int(-1),
+            Value::Int(1),
+            Value::UInt(1),
+            Value::Float(1.5),
+            Value::from("foo"),
+            Value::Bytes(vec![0xff]),
+            Value::Date(2019, 11, 27, 12, 30, 0, 0),
+            Value::Time(true, 1, 2, 3, 4, 5),
+            Value::NULL,
+        ];
+
+        let mut histogram = Histogram::default();
+        for value in values.iter() {
+            value.accept(&mut histogram);
+        }
+
+        let expected = [
+            (ValueKind::NULL, 2),
+            (ValueKind::Int, 2),
+            (ValueKind::UInt, 1),
+            (ValueKind::Float, 1),
+            (ValueKind::Bytes, 2),
+            (ValueKind::Date, 1),
+            (ValueKind::Time, 1),
+        ];
+        assert_eq!(histogram.kinds, expected.iter().cloned().collect());
+        assert_eq!(histogram.bytes, 4);
+    }
+}